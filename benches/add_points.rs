@@ -0,0 +1,231 @@
+use criterion::{criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "fnntw")]
+use kd_tree_comparison::adapters::Fnntw;
+#[cfg(feature = "hnsw_rs")]
+use kd_tree_comparison::adapters::HnswRs;
+#[cfg(feature = "instant-distance")]
+use kd_tree_comparison::adapters::InstantDistance;
+#[cfg(feature = "kd-tree")]
+use kd_tree_comparison::adapters::KdTree;
+#[cfg(feature = "kdtree")]
+use kd_tree_comparison::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "nabo")]
+use kd_tree_comparison::adapters::Nabo;
+#[cfg(feature = "petal-neighbors")]
+use kd_tree_comparison::adapters::PetalBallTree;
+#[cfg(feature = "rstar")]
+use kd_tree_comparison::adapters::Rstar;
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+#[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+use kd_tree_comparison::scalar::FXP;
+use kd_tree_comparison::scenarios::{
+    bench_add_to_empty, SIZES, SIZES_ANN, SIZES_BRUTE_FORCE, SIZES_IMMUTABLE_F32,
+};
+
+pub fn add_to_empty(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Add to Empty Tree");
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    #[cfg(feature = "kiddo_v1")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV1,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV2,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV2Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV3,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV3Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV3Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV3Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV5,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV5Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV5Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoV5Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoNext,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoNextImmutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KiddoNextImmutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32
+    );
+    #[cfg(feature = "fnntw")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        Fnntw,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "nabo")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        Nabo,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "rstar")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        Rstar,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kdtree")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        Kdtree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kd-tree")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        KdTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "petal-neighbors")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        PetalBallTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "hnsw_rs")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        HnswRs,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN
+    );
+    #[cfg(feature = "instant-distance")]
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        InstantDistance,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN
+    );
+    bench_matrix!(
+        group,
+        bench_add_to_empty,
+        BruteForce,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_BRUTE_FORCE
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, add_to_empty);
+criterion_main!(benches);
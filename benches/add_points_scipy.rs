@@ -4,8 +4,10 @@ use criterion::{
 };
 use criterion_polyglot::{BenchSpec, CriterionPolyglotExt};
 
+use kd_tree_comparison::adapters::{leaf_size, leaf_size_tag};
+use kd_tree_comparison::dataset::{dataset_available, dataset_tags, ensure_dataset, DatasetRole};
+use kd_tree_comparison::scenarios::size_within_dims_cap;
 use kiddo_v2::batch_benches;
-use rand::distributions::{Distribution, Standard};
 
 macro_rules! bench_empty_float {
     ($group:ident, $a:ty, $t:ty, $k:tt, $idx: ty, $size:tt, $subtype: expr) => {
@@ -33,6 +35,26 @@ pub fn add_to_empty(c: &mut Criterion) {
             (10_000_000, u32, u32)
         ]
     );
+    batch_benches!(
+        group,
+        bench_empty_float,
+        [
+            (f64, 6),
+            (f64, 8),
+            (f64, 12),
+            (f64, 16),
+            (f64, 32),
+            (f64, 64)
+        ],
+        [
+            (100, u16, u16),
+            (1_000, u16, u16),
+            (10_000, u16, u16),
+            (100_000, u32, u16),
+            (1_000_000, u32, u32),
+            (10_000_000, u32, u32)
+        ]
+    );
 
     group.finish();
 }
@@ -41,23 +63,33 @@ fn bench_add_to_empty_float<const K: usize>(
     group: &mut BenchmarkGroup<WallTime>,
     qty_to_add: usize,
     subtype: &str,
-) where
-    Standard: Distribution<[f64; K]>,
-{
+) {
+    if !dataset_available(K, qty_to_add) || !size_within_dims_cap(K, qty_to_add) {
+        return;
+    }
+
+    let leaf_size = leaf_size();
+
     group.python_benchmark(
-        &*format!("{}/{}", &subtype, &qty_to_add),
-        BenchSpec::new(
+        &*format!(
+            "{}{}{}/{}",
+            &subtype,
+            leaf_size_tag(leaf_size),
+            dataset_tags(),
+            &qty_to_add
+        ),
+        BenchSpec::new(&*format!(
             r#"
-kd_tree = KDTree(data_pts)
+kd_tree = KDTree(data_pts, leafsize={leaf_size})
         "#,
-        )
+        ))
         .with_global_init(&*format!(
             r#"
 from scipy.spatial import KDTree
 import numpy as np
-data_pts = np.random.rand({}, {})
+data_pts = np.load("{}")
         "#,
-            &qty_to_add, K
+            ensure_dataset::<K>(DatasetRole::Points, qty_to_add).display()
         )),
     );
 }
@@ -0,0 +1,170 @@
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+#[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+use kd_tree_comparison::scalar::FXP;
+use kd_tree_comparison::scenarios::{
+    bench_best_n_within, BEST_N_RADIUS, QUERY_POINTS_PER_LOOP, SIZES, SIZES_BRUTE_FORCE,
+    SIZES_IMMUTABLE_F32,
+};
+
+pub fn best_10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Best 10");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    #[cfg(feature = "kiddo_v1")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV1,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV2,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV2Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV3,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV3Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV3Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV3Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV5,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV5Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV5Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV5Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoNext,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoNextImmutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoNextImmutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (BEST_N_RADIUS, 10)
+    );
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        BruteForce,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_BRUTE_FORCE,
+        (BEST_N_RADIUS, 10)
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, best_10);
+criterion_main!(benches);
@@ -0,0 +1,182 @@
+//! Fixed point trees over each of the fixed point types in [`kd_tree_comparison::scalar`],
+//! alongside the float trees of the same kiddo versions built and queried with the same
+//! coordinates, rounded to that fixed point type. The float results are tagged
+//! ` quantized=<type>`.
+//!
+//! `FXP` trees are already run by the main benches, so only its float comparisons run here.
+
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Fixed};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Fixed};
+use kd_tree_comparison::bench_matrix;
+use kd_tree_comparison::dataset::with_quantization;
+use kd_tree_comparison::scalar::{
+    FXP, I16F16, I1F31, I8F24, U0F32, U0F8, U16F16, U4F12, U4F4, U8F24, U8F8,
+};
+use kd_tree_comparison::scenarios::{
+    bench_add_to_empty, bench_best_n_within, bench_nearest_n, bench_nearest_one, bench_within,
+    BEST_N_RADIUS, QUERY_POINTS_PER_LOOP, RADIUS, RADIUS_QUERY_POINTS_PER_LOOP, SIZES_FIXED,
+};
+
+/// Runs a scenario for the float trees, on coordinates rounded to `$fixed`
+macro_rules! quantized_float_trees {
+    ($group:ident, $scenario:ident, $fixed:ty $(, $args:tt)?) => {
+        with_quantization::<$fixed, _>(|| {
+            #[cfg(feature = "kiddo_v2")]
+            bench_matrix!(
+                $group,
+                $scenario,
+                KiddoV2,
+                [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                SIZES_FIXED
+                $(, $args)?
+            );
+            #[cfg(feature = "kiddo_v3")]
+            bench_matrix!(
+                $group,
+                $scenario,
+                KiddoV3,
+                [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                SIZES_FIXED
+                $(, $args)?
+            );
+            #[cfg(feature = "kiddo_v5")]
+            bench_matrix!(
+                $group,
+                $scenario,
+                KiddoV5,
+                [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                SIZES_FIXED
+                $(, $args)?
+            );
+        });
+    };
+}
+
+/// Runs a scenario for the fixed point trees over `$fixed`, and for the float trees on
+/// coordinates rounded to `$fixed`
+macro_rules! fixed_vs_float {
+    ($group:ident, $scenario:ident, $fixed:ty $(, $args:tt)?) => {
+        #[cfg(feature = "kiddo_v2")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV2Fixed,
+            [($fixed, 2), ($fixed, 3), ($fixed, 4)],
+            SIZES_FIXED
+            $(, $args)?
+        );
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV3Fixed,
+            [($fixed, 2), ($fixed, 3), ($fixed, 4)],
+            SIZES_FIXED
+            $(, $args)?
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV5Fixed,
+            [($fixed, 2), ($fixed, 3), ($fixed, 4)],
+            SIZES_FIXED
+            $(, $args)?
+        );
+        quantized_float_trees!($group, $scenario, $fixed $(, $args)?);
+    };
+}
+
+/// Runs a scenario across every fixed point type
+macro_rules! fixed_matrix {
+    ($group:ident, $scenario:ident $(, $args:tt)?) => {
+        quantized_float_trees!($group, $scenario, FXP $(, $args)?);
+        fixed_vs_float!($group, $scenario, U0F8 $(, $args)?);
+        fixed_vs_float!($group, $scenario, U4F4 $(, $args)?);
+        fixed_vs_float!($group, $scenario, U8F8 $(, $args)?);
+        fixed_vs_float!($group, $scenario, U4F12 $(, $args)?);
+        fixed_vs_float!($group, $scenario, U16F16 $(, $args)?);
+        fixed_vs_float!($group, $scenario, U8F24 $(, $args)?);
+        fixed_vs_float!($group, $scenario, U0F32 $(, $args)?);
+        fixed_vs_float!($group, $scenario, I16F16 $(, $args)?);
+        fixed_vs_float!($group, $scenario, I8F24 $(, $args)?);
+        fixed_vs_float!($group, $scenario, I1F31 $(, $args)?);
+    };
+}
+
+pub fn add_to_empty(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Add to Empty Tree");
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    fixed_matrix!(group, bench_add_to_empty);
+
+    group.finish();
+}
+
+pub fn nearest_one(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 1");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    fixed_matrix!(group, bench_nearest_one);
+
+    group.finish();
+}
+
+pub fn nearest_10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 10");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    fixed_matrix!(group, bench_nearest_n, (10));
+
+    group.finish();
+}
+
+pub fn within(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query within radius");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    fixed_matrix!(group, bench_within, (RADIUS));
+
+    group.finish();
+}
+
+pub fn best_10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Best 10");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    fixed_matrix!(group, bench_best_n_within, (BEST_N_RADIUS, 10));
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    add_to_empty,
+    nearest_one,
+    nearest_10,
+    within,
+    best_10
+);
+criterion_main!(benches);
@@ -0,0 +1,379 @@
+//! The high dimensional sweep: every library with const generic dimensions, from 6 up to
+//! 64 dimensions, alongside the brute force baseline so that it shows where each tree degrades
+//! towards a linear scan. Every query of the main benches is run, for the libraries that
+//! support it. Removal and the mixed workload only run in 2 to 4 dimensions, as they compare
+//! kiddo's mutable trees with each other rather than with the other libraries.
+//!
+//! Tree sizes are capped by [`size_within_dims_cap`](kd_tree_comparison::scenarios::size_within_dims_cap),
+//! so the largest trees are only built in the lower dimensions.
+
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "fnntw")]
+use kd_tree_comparison::adapters::Fnntw;
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::KiddoV2;
+#[cfg(feature = "nabo")]
+use kd_tree_comparison::adapters::Nabo;
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+use kd_tree_comparison::scenarios::{
+    bench_add_to_empty, bench_best_n_within, bench_nearest_n, bench_nearest_n_within,
+    bench_nearest_one, bench_within, bench_within_unsorted, BEST_N_RADIUS, QUERY_POINTS_PER_LOOP,
+    RADIUS, RADIUS_QUERY_POINTS_PER_LOOP, SIZES, SIZES_BRUTE_FORCE, SIZES_IMMUTABLE_F32,
+};
+
+/// Runs a scenario for kiddo v3 and v5 and the brute force baseline across the high
+/// dimensional sweep. They support every query, so the other libraries are added per scenario.
+macro_rules! kiddo_high_dims_matrix {
+    ($group:ident, $scenario:ident $(, $args:tt)?) => {
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV3,
+            [
+                (f32, 6), (f64, 6), (f32, 8), (f64, 8), (f32, 12), (f64, 12),
+                (f32, 16), (f64, 16), (f32, 32), (f64, 32), (f32, 64), (f64, 64)
+            ],
+            SIZES
+            $(, $args)?
+        );
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV3Immutable,
+            [(f64, 6), (f64, 8), (f64, 12), (f64, 16), (f64, 32), (f64, 64)],
+            SIZES
+            $(, $args)?
+        );
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV3Immutable,
+            [(f32, 6), (f32, 8), (f32, 12), (f32, 16), (f32, 32), (f32, 64)],
+            SIZES_IMMUTABLE_F32
+            $(, $args)?
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV5,
+            [
+                (f32, 6), (f64, 6), (f32, 8), (f64, 8), (f32, 12), (f64, 12),
+                (f32, 16), (f64, 16), (f32, 32), (f64, 32), (f32, 64), (f64, 64)
+            ],
+            SIZES
+            $(, $args)?
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV5Immutable,
+            [(f64, 6), (f64, 8), (f64, 12), (f64, 16), (f64, 32), (f64, 64)],
+            SIZES
+            $(, $args)?
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV5Immutable,
+            [(f32, 6), (f32, 8), (f32, 12), (f32, 16), (f32, 32), (f32, 64)],
+            SIZES_IMMUTABLE_F32
+            $(, $args)?
+        );
+        bench_matrix!(
+            $group,
+            $scenario,
+            BruteForce,
+            [
+                (f32, 6), (f64, 6), (f32, 8), (f64, 8), (f32, 12), (f64, 12),
+                (f32, 16), (f64, 16), (f32, 32), (f64, 32), (f32, 64), (f64, 64)
+            ],
+            SIZES_BRUTE_FORCE
+            $(, $args)?
+        );
+    };
+}
+
+/// Runs a scenario for every library across the high dimensional sweep
+macro_rules! high_dims_matrix {
+    ($group:ident, $scenario:ident $(, $args:tt)?) => {
+        #[cfg(feature = "kiddo_v2")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV2,
+            [
+                (f32, 6), (f64, 6), (f32, 8), (f64, 8), (f32, 12), (f64, 12),
+                (f32, 16), (f64, 16), (f32, 32), (f64, 32), (f32, 64), (f64, 64)
+            ],
+            SIZES
+            $(, $args)?
+        );
+        kiddo_high_dims_matrix!($group, $scenario $(, $args)?);
+        #[cfg(feature = "fnntw")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            Fnntw,
+            [(f64, 6), (f64, 8), (f64, 12), (f64, 16), (f64, 32), (f64, 64)],
+            SIZES
+            $(, $args)?
+        );
+        #[cfg(feature = "nabo")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            Nabo,
+            [
+                (f32, 6), (f64, 6), (f32, 8), (f64, 8), (f32, 12), (f64, 12),
+                (f32, 16), (f64, 16), (f32, 32), (f64, 32), (f32, 64), (f64, 64)
+            ],
+            SIZES
+            $(, $args)?
+        );
+    };
+}
+
+pub fn add_to_empty(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Add to Empty Tree");
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    high_dims_matrix!(group, bench_add_to_empty);
+
+    group.finish();
+}
+
+pub fn nearest_one(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 1");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    high_dims_matrix!(group, bench_nearest_one);
+
+    group.finish();
+}
+
+pub fn nearest_10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 10");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    high_dims_matrix!(group, bench_nearest_n, (10));
+
+    group.finish();
+}
+
+pub fn nearest_100(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 100");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    high_dims_matrix!(group, bench_nearest_n, (100));
+
+    group.finish();
+}
+
+pub fn within(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query within radius");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    kiddo_high_dims_matrix!(group, bench_within, (RADIUS));
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV2,
+        [
+            (f32, 6),
+            (f64, 6),
+            (f32, 8),
+            (f64, 8),
+            (f32, 12),
+            (f64, 12),
+            (f32, 16),
+            (f64, 16),
+            (f32, 32),
+            (f64, 32),
+            (f32, 64),
+            (f64, 64)
+        ],
+        SIZES,
+        (RADIUS)
+    );
+
+    group.finish();
+}
+
+pub fn within_unsorted(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query within radius unsorted");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    kiddo_high_dims_matrix!(group, bench_within_unsorted, (RADIUS));
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_within_unsorted,
+        KiddoV2,
+        [
+            (f32, 6),
+            (f64, 6),
+            (f32, 8),
+            (f64, 8),
+            (f32, 12),
+            (f64, 12),
+            (f32, 16),
+            (f64, 16),
+            (f32, 32),
+            (f64, 32),
+            (f32, 64),
+            (f64, 64)
+        ],
+        SIZES,
+        (RADIUS)
+    );
+
+    group.finish();
+}
+
+pub fn nearest_n_within(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query nearest n within radius");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    kiddo_high_dims_matrix!(group, bench_nearest_n_within, (RADIUS, true));
+    #[cfg(feature = "nabo")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        Nabo,
+        [
+            (f32, 6),
+            (f64, 6),
+            (f32, 8),
+            (f64, 8),
+            (f32, 12),
+            (f64, 12),
+            (f32, 16),
+            (f64, 16),
+            (f32, 32),
+            (f64, 32),
+            (f32, 64),
+            (f64, 64)
+        ],
+        SIZES,
+        (RADIUS, true)
+    );
+
+    group.finish();
+}
+
+pub fn nearest_n_within_unsorted(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query nearest n within radius unsorted");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    kiddo_high_dims_matrix!(group, bench_nearest_n_within, (RADIUS, false));
+    #[cfg(feature = "nabo")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        Nabo,
+        [
+            (f32, 6),
+            (f64, 6),
+            (f32, 8),
+            (f64, 8),
+            (f32, 12),
+            (f64, 12),
+            (f32, 16),
+            (f64, 16),
+            (f32, 32),
+            (f64, 32),
+            (f32, 64),
+            (f64, 64)
+        ],
+        SIZES,
+        (RADIUS, false)
+    );
+
+    group.finish();
+}
+
+pub fn best_10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Best 10");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    kiddo_high_dims_matrix!(group, bench_best_n_within, (BEST_N_RADIUS, 10));
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_best_n_within,
+        KiddoV2,
+        [
+            (f32, 6),
+            (f64, 6),
+            (f32, 8),
+            (f64, 8),
+            (f32, 12),
+            (f64, 12),
+            (f32, 16),
+            (f64, 16),
+            (f32, 32),
+            (f64, 32),
+            (f32, 64),
+            (f64, 64)
+        ],
+        SIZES,
+        (BEST_N_RADIUS, 10)
+    );
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    add_to_empty,
+    nearest_one,
+    nearest_10,
+    nearest_100,
+    within,
+    within_unsorted,
+    nearest_n_within,
+    nearest_n_within_unsorted,
+    best_10
+);
+criterion_main!(benches);
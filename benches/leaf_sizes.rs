@@ -0,0 +1,215 @@
+//! Sweep of the leaf (bucket) size of every library that exposes one, over [`LEAF_SIZE_SWEEP`],
+//! for construction, nearest_one and nearest_n. Every id is tagged with the leaf size, eg
+//! ` leaf=64`, the default of 32 included.
+//!
+//! kiddo's bucket size is a const generic, so its trees are instantiated once per leaf size.
+//! The other libraries take it at runtime, from [`set_leaf_size`].
+
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+#[cfg(feature = "fnntw")]
+use kd_tree_comparison::adapters::Fnntw;
+#[cfg(feature = "kdtree")]
+use kd_tree_comparison::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::KiddoV2;
+#[cfg(feature = "nabo")]
+use kd_tree_comparison::adapters::Nabo;
+use kd_tree_comparison::adapters::{set_leaf_size, set_tag_leaf_sizes};
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+use kd_tree_comparison::scenarios::{
+    bench_add_to_empty, bench_nearest_n, bench_nearest_one, LEAF_SIZE_SWEEP, QUERY_POINTS_PER_LOOP,
+    SIZES_SWEEP,
+};
+
+/// Runs a scenario for kiddo's trees, at each of the leaf sizes listed, which must match
+/// [`LEAF_SIZE_SWEEP`]. They have to be literals, as each becomes a const generic.
+macro_rules! kiddo_leaf_sizes {
+    ($group:ident, $scenario:ident, $args:tt, [$($b:literal),+ $(,)?]) => {
+        $(
+            {
+                #[cfg(feature = "kiddo_v2")]
+                type KiddoV2Leaf<A, const K: usize> = KiddoV2<A, K, $b>;
+                #[cfg(feature = "kiddo_v3")]
+                type KiddoV3Leaf<A, const K: usize> = KiddoV3<A, K, $b>;
+                #[cfg(feature = "kiddo_v3")]
+                type KiddoV3ImmutableLeaf<A, const K: usize> = KiddoV3Immutable<A, K, $b>;
+                #[cfg(feature = "kiddo_v5")]
+                type KiddoV5Leaf<A, const K: usize> = KiddoV5<A, K, $b>;
+                #[cfg(feature = "kiddo_v5")]
+                type KiddoV5ImmutableLeaf<A, const K: usize> = KiddoV5Immutable<A, K, $b>;
+                #[cfg(feature = "kiddo_next")]
+                type KiddoNextLeaf<A, const K: usize> = KiddoNext<A, K, $b>;
+                #[cfg(feature = "kiddo_next")]
+                type KiddoNextImmutableLeaf<A, const K: usize> = KiddoNextImmutable<A, K, $b>;
+
+                #[cfg(feature = "kiddo_v2")]
+                bench_matrix!(
+                    $group,
+                    $scenario,
+                    KiddoV2Leaf,
+                    [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                    SIZES_SWEEP,
+                    $args
+                );
+                #[cfg(feature = "kiddo_v3")]
+                bench_matrix!(
+                    $group,
+                    $scenario,
+                    KiddoV3Leaf,
+                    [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                    SIZES_SWEEP,
+                    $args
+                );
+                #[cfg(feature = "kiddo_v3")]
+                bench_matrix!(
+                    $group,
+                    $scenario,
+                    KiddoV3ImmutableLeaf,
+                    [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                    SIZES_SWEEP,
+                    $args
+                );
+                #[cfg(feature = "kiddo_v5")]
+                bench_matrix!(
+                    $group,
+                    $scenario,
+                    KiddoV5Leaf,
+                    [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                    SIZES_SWEEP,
+                    $args
+                );
+                #[cfg(feature = "kiddo_v5")]
+                bench_matrix!(
+                    $group,
+                    $scenario,
+                    KiddoV5ImmutableLeaf,
+                    [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                    SIZES_SWEEP,
+                    $args
+                );
+                #[cfg(feature = "kiddo_next")]
+                bench_matrix!(
+                    $group,
+                    $scenario,
+                    KiddoNextLeaf,
+                    [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                    SIZES_SWEEP,
+                    $args
+                );
+                #[cfg(feature = "kiddo_next")]
+                bench_matrix!(
+                    $group,
+                    $scenario,
+                    KiddoNextImmutableLeaf,
+                    [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                    SIZES_SWEEP,
+                    $args
+                );
+            }
+        )+
+    };
+}
+
+/// Runs a scenario for every library that exposes a leaf size, at each leaf size
+macro_rules! leaf_size_matrix {
+    ($group:ident, $scenario:ident, $args:tt) => {
+        set_tag_leaf_sizes(true);
+
+        for leaf_size in LEAF_SIZE_SWEEP {
+            set_leaf_size(leaf_size);
+
+            #[cfg(feature = "kiddo_v1")]
+            bench_matrix!(
+                $group,
+                $scenario,
+                KiddoV1,
+                [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                SIZES_SWEEP,
+                $args
+            );
+            #[cfg(feature = "fnntw")]
+            bench_matrix!(
+                $group,
+                $scenario,
+                Fnntw,
+                [(f64, 2), (f64, 3), (f64, 4)],
+                SIZES_SWEEP,
+                $args
+            );
+            #[cfg(feature = "nabo")]
+            bench_matrix!(
+                $group,
+                $scenario,
+                Nabo,
+                [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                SIZES_SWEEP,
+                $args
+            );
+            #[cfg(feature = "kdtree")]
+            bench_matrix!(
+                $group,
+                $scenario,
+                Kdtree,
+                [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                SIZES_SWEEP,
+                $args
+            );
+        }
+
+        kiddo_leaf_sizes!($group, $scenario, $args, [8, 16, 32, 64, 128, 256]);
+    };
+}
+
+pub fn add_to_empty_leaf_sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Add to Empty Tree");
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    leaf_size_matrix!(group, bench_add_to_empty, ());
+
+    group.finish();
+}
+
+pub fn nearest_one_leaf_sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 1");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    leaf_size_matrix!(group, bench_nearest_one, ());
+
+    group.finish();
+}
+
+pub fn nearest_10_leaf_sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 10");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    leaf_size_matrix!(group, bench_nearest_n, (10));
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    add_to_empty_leaf_sizes,
+    nearest_one_leaf_sizes,
+    nearest_10_leaf_sizes
+);
+criterion_main!(benches);
@@ -0,0 +1,82 @@
+//! Mixed workloads of adds, nearest_n queries and removes, replayed against populated trees
+//! for every library with a mutable tree. Each mix in [`WORKLOAD_MIXES`] runs separately,
+//! tagged ` mix=<add>:<nearest_n>:<remove>`, and is measured in operations per second.
+
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::KiddoV2;
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::KiddoV3;
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::KiddoV5;
+use kd_tree_comparison::bench_matrix;
+use kd_tree_comparison::scenarios::{
+    bench_mixed_workload, SIZES, SIZES_BRUTE_FORCE, WORKLOAD_OPS_PER_LOOP,
+};
+use kd_tree_comparison::workload::WORKLOAD_MIXES;
+
+pub fn mixed_workload(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Mixed Workload");
+    group.throughput(Throughput::Elements(WORKLOAD_OPS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for mix in WORKLOAD_MIXES {
+        #[cfg(feature = "kiddo_v1")]
+        bench_matrix!(
+            group,
+            bench_mixed_workload,
+            KiddoV1,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            SIZES,
+            (mix)
+        );
+        #[cfg(feature = "kiddo_v2")]
+        bench_matrix!(
+            group,
+            bench_mixed_workload,
+            KiddoV2,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            SIZES,
+            (mix)
+        );
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            group,
+            bench_mixed_workload,
+            KiddoV3,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            SIZES,
+            (mix)
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            group,
+            bench_mixed_workload,
+            KiddoV5,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            SIZES,
+            (mix)
+        );
+        bench_matrix!(
+            group,
+            bench_mixed_workload,
+            BruteForce,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            SIZES_BRUTE_FORCE,
+            (mix)
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, mixed_workload);
+criterion_main!(benches);
@@ -0,0 +1,475 @@
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "fnntw")]
+use kd_tree_comparison::adapters::Fnntw;
+#[cfg(feature = "hnsw_rs")]
+use kd_tree_comparison::adapters::HnswRs;
+#[cfg(feature = "instant-distance")]
+use kd_tree_comparison::adapters::InstantDistance;
+#[cfg(feature = "kd-tree")]
+use kd_tree_comparison::adapters::KdTree;
+#[cfg(feature = "kdtree")]
+use kd_tree_comparison::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "nabo")]
+use kd_tree_comparison::adapters::Nabo;
+#[cfg(feature = "petal-neighbors")]
+use kd_tree_comparison::adapters::PetalBallTree;
+#[cfg(feature = "rstar")]
+use kd_tree_comparison::adapters::Rstar;
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+#[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+use kd_tree_comparison::scalar::FXP;
+use kd_tree_comparison::scenarios::{
+    bench_nearest_n, QUERY_POINTS_PER_LOOP, SIZES, SIZES_ANN, SIZES_BRUTE_FORCE,
+    SIZES_IMMUTABLE_F32,
+};
+
+pub fn nearest_10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 10");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    #[cfg(feature = "kiddo_v1")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV1,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV2,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV2Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (10)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoNext,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoNextImmutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoNextImmutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (10)
+    );
+    #[cfg(feature = "fnntw")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Fnntw,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "nabo")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Nabo,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "rstar")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Rstar,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kdtree")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Kdtree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "kd-tree")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KdTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "petal-neighbors")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        PetalBallTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (10)
+    );
+    #[cfg(feature = "hnsw_rs")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        HnswRs,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN,
+        (10)
+    );
+    #[cfg(feature = "instant-distance")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        InstantDistance,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN,
+        (10)
+    );
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        BruteForce,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_BRUTE_FORCE,
+        (10)
+    );
+
+    group.finish();
+}
+
+pub fn nearest_100(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 100");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    #[cfg(feature = "kiddo_v1")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV1,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV2,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV2Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV3Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoV5Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (100)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoNext,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoNextImmutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KiddoNextImmutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (100)
+    );
+    #[cfg(feature = "fnntw")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Fnntw,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "nabo")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Nabo,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "rstar")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Rstar,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kdtree")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        Kdtree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "kd-tree")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        KdTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "petal-neighbors")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        PetalBallTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (100)
+    );
+    #[cfg(feature = "hnsw_rs")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        HnswRs,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN,
+        (100)
+    );
+    #[cfg(feature = "instant-distance")]
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        InstantDistance,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN,
+        (100)
+    );
+    bench_matrix!(
+        group,
+        bench_nearest_n,
+        BruteForce,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_BRUTE_FORCE,
+        (100)
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, nearest_10, nearest_100);
+criterion_main!(benches);
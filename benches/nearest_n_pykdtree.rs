@@ -5,9 +5,13 @@ use criterion::{
 };
 use criterion_polyglot::{BenchSpec, CriterionPolyglotExt};
 
+use kd_tree_comparison::adapters::{leaf_size, leaf_size_tag};
+use kd_tree_comparison::dataset::{dataset_available, dataset_tags, ensure_dataset, DatasetRole};
+use kd_tree_comparison::scenarios::{
+    parameter_tags, size_within_dims_cap, with_parameter_tag, K_SWEEP, SIZES_SWEEP,
+};
 use kiddo_v2::batch_benches;
 use num_traits::Float;
-use rand::distributions::{Distribution, Standard};
 
 const QUERY_POINTS_PER_LOOP: usize = 1_000;
 
@@ -20,10 +24,11 @@ fn rust_float_to_py(rust_float_type_name: &str) -> String {
 
 macro_rules! bench_float_10 {
     ($group:ident, $a:ty, $t:ty, $k:tt, $idx: ty, $size:tt, $subtype: expr) => {
-        bench_query_nearest_10_float::<$a, $k>(
+        bench_query_nearest_n_float::<$a, $k>(
             &mut $group,
             $size,
             QUERY_POINTS_PER_LOOP,
+            10,
             &format!("pykdtree {}", $subtype),
         );
     };
@@ -31,10 +36,11 @@ macro_rules! bench_float_10 {
 
 macro_rules! bench_float_100 {
     ($group:ident, $a:ty, $t:ty, $k:tt, $idx: ty, $size:tt, $subtype: expr) => {
-        bench_query_nearest_100_float::<$a, $k>(
+        bench_query_nearest_n_float::<$a, $k>(
             &mut $group,
             $size,
             QUERY_POINTS_PER_LOOP,
+            100,
             &format!("pykdtree {}", $subtype),
         );
     };
@@ -60,6 +66,32 @@ pub fn nearest_10(c: &mut Criterion) {
             (10_000_000, u32, u32)
         ]
     );
+    batch_benches!(
+        group,
+        bench_float_10,
+        [
+            (f32, 6),
+            (f32, 8),
+            (f32, 12),
+            (f32, 16),
+            (f32, 32),
+            (f32, 64),
+            (f64, 6),
+            (f64, 8),
+            (f64, 12),
+            (f64, 16),
+            (f64, 32),
+            (f64, 64)
+        ],
+        [
+            (100, u16, u16),
+            (1_000, u16, u16),
+            (10_000, u16, u16),
+            (100_000, u32, u16),
+            (1_000_000, u32, u32),
+            (10_000_000, u32, u32)
+        ]
+    );
 
     group.finish();
 }
@@ -84,79 +116,114 @@ pub fn nearest_100(c: &mut Criterion) {
             (10_000_000, u32, u32)
         ]
     );
+    batch_benches!(
+        group,
+        bench_float_100,
+        [
+            (f32, 6),
+            (f32, 8),
+            (f32, 12),
+            (f32, 16),
+            (f32, 32),
+            (f32, 64),
+            (f64, 6),
+            (f64, 8),
+            (f64, 12),
+            (f64, 16),
+            (f64, 32),
+            (f64, 64)
+        ],
+        [
+            (100, u16, u16),
+            (1_000, u16, u16),
+            (10_000, u16, u16),
+            (100_000, u32, u16),
+            (1_000_000, u32, u32),
+            (10_000_000, u32, u32)
+        ]
+    );
 
     group.finish();
 }
 
-fn bench_query_nearest_10_float<A: Float, const K: usize>(
-    group: &mut BenchmarkGroup<WallTime>,
-    initial_size: usize,
-    query_point_qty: usize,
-    subtype: &str,
-) where
-    Standard: Distribution<[f64; K]>,
-{
-    group.python_benchmark(
-        &*format!("{}/{}", &subtype, &initial_size),
-        BenchSpec::new(
-            r#"
-dist, idx = kd_tree.query(query_pts, k=10)
-        "#,
-        )
-        .with_global_init(&*format!(
-            r#"
-from pykdtree.kdtree import KDTree
-import numpy as np
+/// The k sweep of the `sweeps` bench, in 3D at [`SIZES_SWEEP`], so that these results line up
+/// with those of the Rust libraries
+pub fn nearest_n_k_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest n");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
 
-data_pts = np.random.rand({}, {}).astype({})
-query_pts = np.random.rand({}, {}).astype({})
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
 
-kd_tree = KDTree(data_pts)
-        "#,
-            &initial_size,
-            K,
-            rust_float_to_py(std::any::type_name::<A>()),
-            &query_point_qty,
-            K,
-            rust_float_to_py(std::any::type_name::<A>())
-        )),
-    );
+    for k in K_SWEEP {
+        with_parameter_tag("k", k, || {
+            for size in SIZES_SWEEP {
+                bench_query_nearest_n_float::<f32, 3>(
+                    &mut group,
+                    size,
+                    QUERY_POINTS_PER_LOOP,
+                    k,
+                    "pykdtree 3D f32",
+                );
+                bench_query_nearest_n_float::<f64, 3>(
+                    &mut group,
+                    size,
+                    QUERY_POINTS_PER_LOOP,
+                    k,
+                    "pykdtree 3D f64",
+                );
+            }
+        });
+    }
+
+    group.finish();
 }
 
-fn bench_query_nearest_100_float<A: Float, const K: usize>(
+fn bench_query_nearest_n_float<A: Float, const K: usize>(
     group: &mut BenchmarkGroup<WallTime>,
     initial_size: usize,
     query_point_qty: usize,
+    k: usize,
     subtype: &str,
-) where
-    Standard: Distribution<[f64; K]>,
-{
+) {
+    if !dataset_available(K, initial_size) || !size_within_dims_cap(K, initial_size) {
+        return;
+    }
+
+    let leaf_size = leaf_size();
+
     group.python_benchmark(
-        &*format!("{}/{}", &subtype, &initial_size),
-        BenchSpec::new(
+        &*format!(
+            "{}{}{}{}/{}",
+            &subtype,
+            leaf_size_tag(leaf_size),
+            parameter_tags(),
+            dataset_tags(),
+            &initial_size
+        ),
+        BenchSpec::new(&*format!(
             r#"
-dist, idx = kd_tree.query(query_pts, k=100)
+dist, idx = kd_tree.query(query_pts, k={})
         "#,
-        )
+            k
+        ))
         .with_global_init(&*format!(
             r#"
 from pykdtree.kdtree import KDTree
 import numpy as np
 
-data_pts = np.random.rand({}, {}).astype({})
-query_pts = np.random.rand({}, {}).astype({})
+data_pts = np.load("{}").astype({})
+query_pts = np.load("{}").astype({})
 
-kd_tree = KDTree(data_pts)
+kd_tree = KDTree(data_pts, leafsize={leaf_size})
         "#,
-            &initial_size,
-            K,
+            ensure_dataset::<K>(DatasetRole::Points, initial_size).display(),
             rust_float_to_py(std::any::type_name::<A>()),
-            &query_point_qty,
-            K,
+            ensure_dataset::<K>(DatasetRole::Queries, query_point_qty).display(),
             rust_float_to_py(std::any::type_name::<A>())
         )),
     );
 }
 
-criterion_group!(benches, nearest_10, nearest_100);
+criterion_group!(benches, nearest_10, nearest_100, nearest_n_k_sweep);
 criterion_main!(benches);
@@ -0,0 +1,130 @@
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "nabo")]
+use kd_tree_comparison::adapters::Nabo;
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+use kd_tree_comparison::scenarios::{
+    bench_nearest_n_within, RADIUS, RADIUS_QUERY_POINTS_PER_LOOP, SIZES, SIZES_BRUTE_FORCE,
+    SIZES_IMMUTABLE_F32,
+};
+
+pub fn nearest_n_within_unsorted(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query nearest n within radius unsorted");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoV3,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoV3Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoV3Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoV5,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoV5Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoV5Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoNext,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoNextImmutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        KiddoNextImmutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (RADIUS, false)
+    );
+    #[cfg(feature = "nabo")]
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        Nabo,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS, false)
+    );
+    bench_matrix!(
+        group,
+        bench_nearest_n_within,
+        BruteForce,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_BRUTE_FORCE,
+        (RADIUS, false)
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, nearest_n_within_unsorted);
+criterion_main!(benches);
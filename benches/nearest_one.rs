@@ -0,0 +1,235 @@
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "fnntw")]
+use kd_tree_comparison::adapters::Fnntw;
+#[cfg(feature = "hnsw_rs")]
+use kd_tree_comparison::adapters::HnswRs;
+#[cfg(feature = "instant-distance")]
+use kd_tree_comparison::adapters::InstantDistance;
+#[cfg(feature = "kd-tree")]
+use kd_tree_comparison::adapters::KdTree;
+#[cfg(feature = "kdtree")]
+use kd_tree_comparison::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "nabo")]
+use kd_tree_comparison::adapters::Nabo;
+#[cfg(feature = "petal-neighbors")]
+use kd_tree_comparison::adapters::PetalBallTree;
+#[cfg(feature = "rstar")]
+use kd_tree_comparison::adapters::Rstar;
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+#[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+use kd_tree_comparison::scalar::FXP;
+use kd_tree_comparison::scenarios::{
+    bench_nearest_one, QUERY_POINTS_PER_LOOP, SIZES, SIZES_ANN, SIZES_BRUTE_FORCE,
+    SIZES_IMMUTABLE_F32,
+};
+
+pub fn nearest_one(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 1");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    #[cfg(feature = "kiddo_v1")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV1,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV2,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV2Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV3,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV3Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV3Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV3Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV5,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV5Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV5Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoV5Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoNext,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoNextImmutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KiddoNextImmutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32
+    );
+    #[cfg(feature = "fnntw")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        Fnntw,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "nabo")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        Nabo,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "rstar")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        Rstar,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kdtree")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        Kdtree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "kd-tree")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        KdTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "petal-neighbors")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        PetalBallTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES
+    );
+    #[cfg(feature = "hnsw_rs")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        HnswRs,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN
+    );
+    #[cfg(feature = "instant-distance")]
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        InstantDistance,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_ANN
+    );
+    bench_matrix!(
+        group,
+        bench_nearest_one,
+        BruteForce,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_BRUTE_FORCE
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, nearest_one);
+criterion_main!(benches);
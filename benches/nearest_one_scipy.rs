@@ -5,9 +5,11 @@ use criterion::{
 };
 use criterion_polyglot::{BenchSpec, CriterionPolyglotExt};
 
+use kd_tree_comparison::adapters::{leaf_size, leaf_size_tag};
+use kd_tree_comparison::dataset::{dataset_available, dataset_tags, ensure_dataset, DatasetRole};
+use kd_tree_comparison::scenarios::size_within_dims_cap;
 use kiddo_v2::batch_benches;
 use num_traits::Float;
-use rand::distributions::{Distribution, Standard};
 
 const QUERY_POINTS_PER_LOOP: usize = 1_000;
 
@@ -49,6 +51,32 @@ pub fn nearest_one(c: &mut Criterion) {
             (10_000_000, u32, u32)
         ]
     );
+    batch_benches!(
+        group,
+        bench_float,
+        [
+            (f32, 6),
+            (f32, 8),
+            (f32, 12),
+            (f32, 16),
+            (f32, 32),
+            (f32, 64),
+            (f64, 6),
+            (f64, 8),
+            (f64, 12),
+            (f64, 16),
+            (f64, 32),
+            (f64, 64)
+        ],
+        [
+            (100, u16, u16),
+            (1_000, u16, u16),
+            (10_000, u16, u16),
+            (100_000, u32, u16),
+            (1_000_000, u32, u32),
+            (10_000_000, u32, u32)
+        ]
+    );
 
     group.finish();
 }
@@ -58,11 +86,21 @@ fn bench_query_nearest_one_float<A: Float, const K: usize>(
     initial_size: usize,
     query_point_qty: usize,
     subtype: &str,
-) where
-    Standard: Distribution<[f64; K]>,
-{
+) {
+    if !dataset_available(K, initial_size) || !size_within_dims_cap(K, initial_size) {
+        return;
+    }
+
+    let leaf_size = leaf_size();
+
     group.python_benchmark(
-        &*format!("{}/{}", &subtype, &initial_size),
+        &*format!(
+            "{}{}{}/{}",
+            &subtype,
+            leaf_size_tag(leaf_size),
+            dataset_tags(),
+            &initial_size
+        ),
         BenchSpec::new(
             r#"
 dist, idx = kd_tree.query(query_pts, k=1) # workers=-1 makes this slower?
@@ -73,16 +111,14 @@ dist, idx = kd_tree.query(query_pts, k=1) # workers=-1 makes this slower?
 from scipy.spatial import KDTree
 import numpy as np
 
-data_pts = np.random.rand({}, {}).astype({})
-query_pts = np.random.rand({}, {}).astype({})
+data_pts = np.load("{}").astype({})
+query_pts = np.load("{}").astype({})
 
-kd_tree = KDTree(data_pts)
+kd_tree = KDTree(data_pts, leafsize={leaf_size})
         "#,
-            &initial_size,
-            K,
+            ensure_dataset::<K>(DatasetRole::Points, initial_size).display(),
             rust_float_to_py(std::any::type_name::<A>()),
-            &query_point_qty,
-            K,
+            ensure_dataset::<K>(DatasetRole::Queries, query_point_qty).display(),
             rust_float_to_py(std::any::type_name::<A>())
         )),
     );
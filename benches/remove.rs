@@ -0,0 +1,115 @@
+//! Removal of 1%, 10% and 50% of a populated tree's points, in random order and in spatially
+//! clustered order, and nearest_n queries against the trees that are left. Results are tagged
+//! with the removal, eg ` removed=10% order=clustered`.
+
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::KiddoV2;
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::KiddoV3;
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::KiddoV5;
+use kd_tree_comparison::bench_matrix;
+use kd_tree_comparison::scenarios::{
+    bench_nearest_n_after_remove, bench_remove, QUERY_POINTS_PER_LOOP,
+};
+use kd_tree_comparison::workload::Removal;
+
+/// Every removal starts from a freshly built tree, so sizes stop short of the full matrix's
+const SIZES_REMOVE: [usize; 4] = [100, 1_000, 10_000, 100_000];
+
+/// Each brute force removal is a linear scan, so removing half of 100K points takes too long
+const SIZES_BRUTE_FORCE_REMOVE: [usize; 3] = [100, 1_000, 10_000];
+
+/// Runs a scenario for every library with a mutable tree, at one size
+macro_rules! removal_matrix {
+    ($group:ident, $scenario:ident, $size:ident, $args:tt) => {
+        #[cfg(feature = "kiddo_v1")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV1,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            [$size],
+            $args
+        );
+        #[cfg(feature = "kiddo_v2")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV2,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            [$size],
+            $args
+        );
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV3,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            [$size],
+            $args
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV5,
+            [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+            [$size],
+            $args
+        );
+        if SIZES_BRUTE_FORCE_REMOVE.contains(&$size) {
+            bench_matrix!(
+                $group,
+                $scenario,
+                BruteForce,
+                [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+                [$size],
+                $args
+            );
+        }
+    };
+}
+
+pub fn remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Remove from Tree");
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for removal in Removal::all() {
+        for size in SIZES_REMOVE {
+            group.throughput(Throughput::Elements(removal.qty(size) as u64));
+            removal_matrix!(group, bench_remove, size, (removal));
+        }
+    }
+
+    group.finish();
+}
+
+pub fn nearest_10_after_remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest 10 after Remove");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for removal in Removal::all() {
+        for size in SIZES_REMOVE {
+            removal_matrix!(group, bench_nearest_n_after_remove, size, (removal, 10));
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, remove, nearest_10_after_remove);
+criterion_main!(benches);
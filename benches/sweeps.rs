@@ -0,0 +1,391 @@
+//! Sweeps of the query parameters that the other benches fix: k, the number of results,
+//! for nearest_n and best_n_within, and the radius of the radius-based queries. Every value in
+//! [`K_SWEEP`] and [`RADIUS_SWEEP`] is run in 3D at [`SIZES_SWEEP`], with ids tagged with the
+//! parameter, eg ` k=50` or ` radius=0.001`.
+
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "fnntw")]
+use kd_tree_comparison::adapters::Fnntw;
+#[cfg(feature = "hnsw_rs")]
+use kd_tree_comparison::adapters::HnswRs;
+#[cfg(feature = "instant-distance")]
+use kd_tree_comparison::adapters::InstantDistance;
+#[cfg(feature = "kd-tree")]
+use kd_tree_comparison::adapters::KdTree;
+#[cfg(feature = "kdtree")]
+use kd_tree_comparison::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::KiddoV2;
+#[cfg(feature = "nabo")]
+use kd_tree_comparison::adapters::Nabo;
+#[cfg(feature = "petal-neighbors")]
+use kd_tree_comparison::adapters::PetalBallTree;
+#[cfg(feature = "rstar")]
+use kd_tree_comparison::adapters::Rstar;
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+use kd_tree_comparison::scenarios::{
+    bench_best_n_within, bench_nearest_n, bench_nearest_n_within, bench_within,
+    bench_within_unsorted, with_parameter_tag, BEST_N_RADIUS, K_SWEEP, QUERY_POINTS_PER_LOOP,
+    RADIUS_QUERY_POINTS_PER_LOOP, RADIUS_SWEEP, SIZES_SWEEP,
+};
+
+/// Runs a scenario for the kiddo trees that support every query in this file
+macro_rules! kiddo_sweep {
+    ($group:ident, $scenario:ident, $args:tt) => {
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV3,
+            [(f32, 3), (f64, 3)],
+            SIZES_SWEEP,
+            $args
+        );
+        #[cfg(feature = "kiddo_v3")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV3Immutable,
+            [(f32, 3), (f64, 3)],
+            SIZES_SWEEP,
+            $args
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV5,
+            [(f32, 3), (f64, 3)],
+            SIZES_SWEEP,
+            $args
+        );
+        #[cfg(feature = "kiddo_v5")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoV5Immutable,
+            [(f32, 3), (f64, 3)],
+            SIZES_SWEEP,
+            $args
+        );
+        #[cfg(feature = "kiddo_next")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoNext,
+            [(f32, 3), (f64, 3)],
+            SIZES_SWEEP,
+            $args
+        );
+        #[cfg(feature = "kiddo_next")]
+        bench_matrix!(
+            $group,
+            $scenario,
+            KiddoNextImmutable,
+            [(f32, 3), (f64, 3)],
+            SIZES_SWEEP,
+            $args
+        );
+        bench_matrix!(
+            $group,
+            $scenario,
+            BruteForce,
+            [(f32, 3), (f64, 3)],
+            SIZES_SWEEP,
+            $args
+        );
+    };
+}
+
+pub fn nearest_n_k_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Nearest n");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for k in K_SWEEP {
+        with_parameter_tag("k", k, || {
+            kiddo_sweep!(group, bench_nearest_n, (k));
+            #[cfg(feature = "kiddo_v1")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                KiddoV1,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "kiddo_v2")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                KiddoV2,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "fnntw")]
+            bench_matrix!(group, bench_nearest_n, Fnntw, [(f64, 3)], SIZES_SWEEP, (k));
+            #[cfg(feature = "nabo")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                Nabo,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "rstar")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                Rstar,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "kdtree")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                Kdtree,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "kd-tree")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                KdTree,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "petal-neighbors")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                PetalBallTree,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "hnsw_rs")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                HnswRs,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+            #[cfg(feature = "instant-distance")]
+            bench_matrix!(
+                group,
+                bench_nearest_n,
+                InstantDistance,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (k)
+            );
+        });
+    }
+
+    group.finish();
+}
+
+pub fn best_n_k_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query Best n");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for k in K_SWEEP {
+        with_parameter_tag("k", k, || {
+            kiddo_sweep!(group, bench_best_n_within, (BEST_N_RADIUS, k));
+            #[cfg(feature = "kiddo_v1")]
+            bench_matrix!(
+                group,
+                bench_best_n_within,
+                KiddoV1,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (BEST_N_RADIUS, k)
+            );
+            #[cfg(feature = "kiddo_v2")]
+            bench_matrix!(
+                group,
+                bench_best_n_within,
+                KiddoV2,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (BEST_N_RADIUS, k)
+            );
+        });
+    }
+
+    group.finish();
+}
+
+pub fn within_radius_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query within radius");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for radius in RADIUS_SWEEP {
+        with_parameter_tag("radius", radius, || {
+            kiddo_sweep!(group, bench_within, (radius));
+            #[cfg(feature = "kiddo_v1")]
+            bench_matrix!(
+                group,
+                bench_within,
+                KiddoV1,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+            #[cfg(feature = "kiddo_v2")]
+            bench_matrix!(
+                group,
+                bench_within,
+                KiddoV2,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+            #[cfg(feature = "rstar")]
+            bench_matrix!(
+                group,
+                bench_within,
+                Rstar,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+            #[cfg(feature = "kdtree")]
+            bench_matrix!(
+                group,
+                bench_within,
+                Kdtree,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+            #[cfg(feature = "kd-tree")]
+            bench_matrix!(
+                group,
+                bench_within,
+                KdTree,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+            #[cfg(feature = "petal-neighbors")]
+            bench_matrix!(
+                group,
+                bench_within,
+                PetalBallTree,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+        });
+    }
+
+    group.finish();
+}
+
+pub fn within_unsorted_radius_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query within radius unsorted");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for radius in RADIUS_SWEEP {
+        with_parameter_tag("radius", radius, || {
+            kiddo_sweep!(group, bench_within_unsorted, (radius));
+            #[cfg(feature = "kiddo_v1")]
+            bench_matrix!(
+                group,
+                bench_within_unsorted,
+                KiddoV1,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+            #[cfg(feature = "kiddo_v2")]
+            bench_matrix!(
+                group,
+                bench_within_unsorted,
+                KiddoV2,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+            #[cfg(feature = "rstar")]
+            bench_matrix!(
+                group,
+                bench_within_unsorted,
+                Rstar,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius)
+            );
+        });
+    }
+
+    group.finish();
+}
+
+pub fn nearest_n_within_radius_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query nearest n within radius");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for radius in RADIUS_SWEEP {
+        with_parameter_tag("radius", radius, || {
+            kiddo_sweep!(group, bench_nearest_n_within, (radius, true));
+            #[cfg(feature = "nabo")]
+            bench_matrix!(
+                group,
+                bench_nearest_n_within,
+                Nabo,
+                [(f32, 3), (f64, 3)],
+                SIZES_SWEEP,
+                (radius, true)
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    nearest_n_k_sweep,
+    best_n_k_sweep,
+    within_radius_sweep,
+    within_unsorted_radius_sweep,
+    nearest_n_within_radius_sweep
+);
+criterion_main!(benches);
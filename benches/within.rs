@@ -0,0 +1,214 @@
+use criterion::{
+    criterion_group, criterion_main, AxisScale, Criterion, PlotConfiguration, Throughput,
+};
+
+use kd_tree_comparison::adapters::BruteForce;
+#[cfg(feature = "kd-tree")]
+use kd_tree_comparison::adapters::KdTree;
+#[cfg(feature = "kdtree")]
+use kd_tree_comparison::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use kd_tree_comparison::adapters::KiddoV1;
+#[cfg(feature = "petal-neighbors")]
+use kd_tree_comparison::adapters::PetalBallTree;
+#[cfg(feature = "rstar")]
+use kd_tree_comparison::adapters::Rstar;
+#[cfg(feature = "kiddo_next")]
+use kd_tree_comparison::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v2")]
+use kd_tree_comparison::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use kd_tree_comparison::adapters::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use kd_tree_comparison::adapters::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+use kd_tree_comparison::bench_matrix;
+#[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+use kd_tree_comparison::scalar::FXP;
+use kd_tree_comparison::scenarios::{
+    bench_within, RADIUS, RADIUS_QUERY_POINTS_PER_LOOP, SIZES, SIZES_BRUTE_FORCE,
+    SIZES_IMMUTABLE_F32,
+};
+
+pub fn within(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query within radius");
+    group.throughput(Throughput::Elements(RADIUS_QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    #[cfg(feature = "kiddo_v1")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV1,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV2,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v2")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV2Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV3,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV3Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV3Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v3")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV3Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV5,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV5Fixed,
+        [(FXP, 2), (FXP, 3), (FXP, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV5Immutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_v5")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoV5Immutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoNext,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoNextImmutable,
+        [(f64, 2), (f64, 3), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kiddo_next")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KiddoNextImmutable,
+        [(f32, 2), (f32, 3), (f32, 4)],
+        SIZES_IMMUTABLE_F32,
+        (RADIUS)
+    );
+    #[cfg(feature = "rstar")]
+    bench_matrix!(
+        group,
+        bench_within,
+        Rstar,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kdtree")]
+    bench_matrix!(
+        group,
+        bench_within,
+        Kdtree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "kd-tree")]
+    bench_matrix!(
+        group,
+        bench_within,
+        KdTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    #[cfg(feature = "petal-neighbors")]
+    bench_matrix!(
+        group,
+        bench_within,
+        PetalBallTree,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES,
+        (RADIUS)
+    );
+    bench_matrix!(
+        group,
+        bench_within,
+        BruteForce,
+        [(f32, 2), (f64, 2), (f32, 3), (f64, 3), (f32, 4), (f64, 4)],
+        SIZES_BRUTE_FORCE,
+        (RADIUS)
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, within);
+criterion_main!(benches);
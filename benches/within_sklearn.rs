@@ -5,9 +5,13 @@ use criterion::{
 };
 use criterion_polyglot::{BenchSpec, CriterionPolyglotExt};
 
+use kd_tree_comparison::adapters::{leaf_size, leaf_size_tag};
+use kd_tree_comparison::dataset::{dataset_available, dataset_tags, ensure_dataset, DatasetRole};
+use kd_tree_comparison::scenarios::{
+    parameter_tags, size_within_dims_cap, with_parameter_tag, RADIUS_SWEEP, SIZES_SWEEP,
+};
 use kiddo_v2::batch_benches;
 use num_traits::Float;
-use rand::distributions::{Distribution, Standard};
 
 const QUERY_POINTS_PER_LOOP: usize = 100;
 const RADIUS: f64 = 0.01;
@@ -26,11 +30,26 @@ macro_rules! bench_float {
             &mut $group,
             $size,
             QUERY_POINTS_PER_LOOP,
+            RADIUS,
+            "KDTree",
             &format!("sklearn {}", $subtype),
         );
     };
 }
 
+macro_rules! bench_ball_tree_float {
+    ($group:ident, $a:ty, $t:ty, $k:tt, $idx: ty, $size:tt, $subtype: expr) => {
+        bench_query_float::<$a, $k>(
+            &mut $group,
+            $size,
+            QUERY_POINTS_PER_LOOP,
+            RADIUS,
+            "BallTree",
+            &format!("sklearn_balltree {}", $subtype),
+        );
+    };
+}
+
 pub fn nearest(c: &mut Criterion) {
     let mut group = c.benchmark_group("Query within radius");
     group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
@@ -51,6 +70,122 @@ pub fn nearest(c: &mut Criterion) {
             (10_000_000, u32, u32)
         ]
     );
+    batch_benches!(
+        group,
+        bench_float,
+        [
+            (f32, 6),
+            (f32, 8),
+            (f32, 12),
+            (f32, 16),
+            (f32, 32),
+            (f32, 64),
+            (f64, 6),
+            (f64, 8),
+            (f64, 12),
+            (f64, 16),
+            (f64, 32),
+            (f64, 64)
+        ],
+        [
+            (100, u16, u16),
+            (1_000, u16, u16),
+            (10_000, u16, u16),
+            (100_000, u32, u16),
+            (1_000_000, u32, u32),
+            (10_000_000, u32, u32)
+        ]
+    );
+    batch_benches!(
+        group,
+        bench_ball_tree_float,
+        [(f32, 2), (f32, 3), (f32, 4), (f64, 2), (f64, 3), (f64, 4)],
+        [
+            (100, u16, u16),
+            (1_000, u16, u16),
+            (10_000, u16, u16),
+            (100_000, u32, u16),
+            (1_000_000, u32, u32),
+            (10_000_000, u32, u32)
+        ]
+    );
+    batch_benches!(
+        group,
+        bench_ball_tree_float,
+        [
+            (f32, 6),
+            (f32, 8),
+            (f32, 12),
+            (f32, 16),
+            (f32, 32),
+            (f32, 64),
+            (f64, 6),
+            (f64, 8),
+            (f64, 12),
+            (f64, 16),
+            (f64, 32),
+            (f64, 64)
+        ],
+        [
+            (100, u16, u16),
+            (1_000, u16, u16),
+            (10_000, u16, u16),
+            (100_000, u32, u16),
+            (1_000_000, u32, u32),
+            (10_000_000, u32, u32)
+        ]
+    );
+
+    group.finish();
+}
+
+/// The radius sweep of the `sweeps` bench, in 3D at [`SIZES_SWEEP`], so that these results
+/// line up with those of the Rust libraries
+pub fn within_radius_sweep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Query within radius");
+    group.throughput(Throughput::Elements(QUERY_POINTS_PER_LOOP as u64));
+
+    let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+    group.plot_config(plot_config);
+
+    for radius in RADIUS_SWEEP {
+        with_parameter_tag("radius", radius, || {
+            for size in SIZES_SWEEP {
+                bench_query_float::<f32, 3>(
+                    &mut group,
+                    size,
+                    QUERY_POINTS_PER_LOOP,
+                    radius,
+                    "KDTree",
+                    "sklearn 3D f32",
+                );
+                bench_query_float::<f64, 3>(
+                    &mut group,
+                    size,
+                    QUERY_POINTS_PER_LOOP,
+                    radius,
+                    "KDTree",
+                    "sklearn 3D f64",
+                );
+                bench_query_float::<f32, 3>(
+                    &mut group,
+                    size,
+                    QUERY_POINTS_PER_LOOP,
+                    radius,
+                    "BallTree",
+                    "sklearn_balltree 3D f32",
+                );
+                bench_query_float::<f64, 3>(
+                    &mut group,
+                    size,
+                    QUERY_POINTS_PER_LOOP,
+                    radius,
+                    "BallTree",
+                    "sklearn_balltree 3D f64",
+                );
+            }
+        });
+    }
 
     group.finish();
 }
@@ -59,37 +194,48 @@ fn bench_query_float<A: Float, const K: usize>(
     group: &mut BenchmarkGroup<WallTime>,
     initial_size: usize,
     query_point_qty: usize,
+    radius: f64,
+    tree_class: &str,
     subtype: &str,
-) where
-    Standard: Distribution<[f64; K]>,
-{
+) {
+    if !dataset_available(K, initial_size) || !size_within_dims_cap(K, initial_size) {
+        return;
+    }
+
+    let leaf_size = leaf_size();
+
     group.python_benchmark(
-        &*format!("{}/{}", &subtype, &initial_size),
+        &*format!(
+            "{}{}{}{}/{}",
+            &subtype,
+            leaf_size_tag(leaf_size),
+            parameter_tags(),
+            dataset_tags(),
+            &initial_size
+        ),
         BenchSpec::new(&*format!(
             r#"
 dist, idx = kd_tree.query_radius(query_pts, r={}, sort_results=True, return_distance=True)
         "#,
-            RADIUS.sqrt(),
+            radius.sqrt(),
         ))
         .with_global_init(&*format!(
             r#"
-from sklearn.neighbors import KDTree
+from sklearn.neighbors import {tree_class}
 import numpy as np
 
-data_pts = np.random.rand({}, {}).astype({})
-query_pts = np.random.rand({}, {}).astype({})
+data_pts = np.load("{}").astype({})
+query_pts = np.load("{}").astype({})
 
-kd_tree = KDTree(data_pts)
+kd_tree = {tree_class}(data_pts, leaf_size={leaf_size})
         "#,
-            &initial_size,
-            K,
+            ensure_dataset::<K>(DatasetRole::Points, initial_size).display(),
             rust_float_to_py(std::any::type_name::<A>()),
-            &query_point_qty,
-            K,
+            ensure_dataset::<K>(DatasetRole::Queries, query_point_qty).display(),
             rust_float_to_py(std::any::type_name::<A>())
         )),
     );
 }
 
-criterion_group!(benches, nearest);
+criterion_group!(benches, nearest, within_radius_sweep);
 criterion_main!(benches);
@@ -0,0 +1,188 @@
+//! An exact linear scan over every point, as the baseline that a tree has to beat, and as
+//! the ground truth that the other libraries' results are checked against.
+//!
+//! Points are scanned in chunks, with the squared distances of a chunk accumulated one
+//! dimension at a time into a fixed size array, so that the inner loop vectorises.
+
+use num_traits::Float;
+
+use crate::adapters::{Item, KdTreeAdapter, Neighbour, Operation};
+use crate::scalar::Scalar;
+
+/// Number of points whose distances are calculated together
+const CHUNK_SIZE: usize = 64;
+
+pub struct BruteForce<A, const K: usize> {
+    points: Vec<[A; K]>,
+    items: Vec<Item>,
+}
+
+impl<A: Scalar + Float, const K: usize> BruteForce<A, K> {
+    /// Calls `f` with the squared distance from `query` of every point, and its index
+    fn for_each_distance(&self, query: &[A; K], mut f: impl FnMut(A, usize)) {
+        for (chunk_idx, chunk) in self.points.chunks(CHUNK_SIZE).enumerate() {
+            let mut distances = [A::zero(); CHUNK_SIZE];
+            for dim in 0..K {
+                for (distance, point) in distances.iter_mut().zip(chunk) {
+                    let diff = point[dim] - query[dim];
+                    *distance = *distance + diff * diff;
+                }
+            }
+
+            for (idx, &distance) in distances[..chunk.len()].iter().enumerate() {
+                f(distance, chunk_idx * CHUNK_SIZE + idx);
+            }
+        }
+    }
+}
+
+fn sort_by_distance<A: Scalar>(neighbours: &mut [Neighbour<A>]) {
+    neighbours.sort_unstable_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+}
+
+impl<A: Scalar + Float, const K: usize> KdTreeAdapter<A, K> for BruteForce<A, K> {
+    const NAME: &'static str = "brute-force";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::Add,
+        Operation::Remove,
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+        Operation::NearestNWithin,
+        Operation::BestNWithin,
+    ];
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        BruteForce {
+            points: points.to_vec(),
+            items: (0..points.len() as Item).collect(),
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        BruteForce {
+            points: Vec::with_capacity(capacity),
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.points.push(*point);
+        self.items.push(item);
+    }
+
+    fn remove(&mut self, point: &[A; K], item: Item) -> usize {
+        let mut removed = 0;
+        for idx in (0..self.points.len()).rev() {
+            if self.items[idx] == item && self.points[idx] == *point {
+                self.points.swap_remove(idx);
+                self.items.swap_remove(idx);
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let mut best = Neighbour {
+            distance: A::infinity(),
+            item: 0,
+        };
+        self.for_each_distance(query, |distance, idx| {
+            if distance < best.distance {
+                best = Neighbour {
+                    distance,
+                    item: self.items[idx],
+                };
+            }
+        });
+
+        best
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        self.nearest_n_within(query, A::infinity(), qty, true)
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        let mut results = self.within_unsorted(query, radius);
+        sort_by_distance(&mut results);
+
+        results
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        let mut results = vec![];
+        self.for_each_distance(query, |distance, idx| {
+            if distance <= radius {
+                results.push(Neighbour {
+                    distance,
+                    item: self.items[idx],
+                });
+            }
+        });
+
+        results
+    }
+
+    /// Keeps the nearest `max_qty` seen so far in order, so the results are always sorted
+    fn nearest_n_within(
+        &self,
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        _sorted: bool,
+    ) -> Vec<Neighbour<A>> {
+        if max_qty == 0 {
+            return Vec::new();
+        }
+
+        let mut results: Vec<Neighbour<A>> = Vec::with_capacity(max_qty.min(self.points.len()));
+        self.for_each_distance(query, |distance, idx| {
+            if distance > radius
+                || (results.len() == max_qty && distance >= results[max_qty - 1].distance)
+            {
+                return;
+            }
+
+            if results.len() == max_qty {
+                results.pop();
+            }
+            let pos = results.partition_point(|n| n.distance <= distance);
+            results.insert(
+                pos,
+                Neighbour {
+                    distance,
+                    item: self.items[idx],
+                },
+            );
+        });
+
+        results
+    }
+
+    /// The lowest `max_qty` items within `radius`, as with kiddo
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        let mut items = vec![];
+        self.for_each_distance(query, |distance, idx| {
+            if distance <= radius {
+                items.push(self.items[idx]);
+            }
+        });
+        items.sort_unstable();
+        items.truncate(max_qty);
+
+        items
+    }
+}
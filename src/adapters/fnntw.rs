@@ -0,0 +1,60 @@
+use ::fnntw::Tree;
+
+use crate::adapters::{leaf_size, Item, KdTreeAdapter, LeafSize, Neighbour, Operation};
+
+/// FNNTW only supports `f64`, so the adapter is only implemented for `Fnntw<f64, K>`.
+pub struct Fnntw<A, const K: usize> {
+    // `tree` borrows from the heap allocation owned by `_points`, so it is declared
+    // first to ensure that it is dropped first
+    tree: Tree<'static, K>,
+    _points: Vec<[A; K]>,
+}
+
+impl<const K: usize> KdTreeAdapter<f64, K> for Fnntw<f64, K> {
+    const NAME: &'static str = "FNNTW";
+    const OPERATIONS: &'static [Operation] = &[Operation::NearestOne, Operation::NearestN];
+    const LEAF_SIZE: LeafSize = LeafSize::Runtime;
+
+    /// FNNTW's tree borrows the points that it is built from, so the adapter
+    /// keeps its own copy alive for as long as the tree
+    type Input<'a> = Vec<[f64; K]>;
+
+    fn prepare(points: &[[f64; K]]) -> Self::Input<'_> {
+        points.to_vec()
+    }
+
+    /// Built with a parallel split level of 1, as FNNTW construction has always been benchmarked
+    fn build(points: Self::Input<'_>) -> Self {
+        // SAFETY: the slice points into the Vec's heap allocation, which does not move
+        // when the Vec does and is never modified. `tree` is dropped before `points`.
+        let data: &'static [[f64; K]] =
+            unsafe { std::slice::from_raw_parts(points.as_ptr(), points.len()) };
+
+        Fnntw {
+            tree: Tree::new_parallel(data, leaf_size(), 1).unwrap(),
+            _points: points,
+        }
+    }
+
+    fn nearest_one(&self, query: &[f64; K]) -> Neighbour<f64> {
+        let (distance, idx, _) = self.tree.query_nearest(query).unwrap();
+
+        Neighbour {
+            distance,
+            item: idx as Item,
+        }
+    }
+
+    fn nearest_n(&self, query: &[f64; K], qty: usize) -> Vec<Neighbour<f64>> {
+        let (distances, indices, _) = self.tree.query_nearest_k(query, qty).unwrap();
+
+        distances
+            .into_iter()
+            .zip(indices)
+            .map(|(distance, idx)| Neighbour {
+                distance,
+                item: idx as Item,
+            })
+            .collect()
+    }
+}
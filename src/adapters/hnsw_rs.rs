@@ -0,0 +1,76 @@
+//! hnsw_rs's Hierarchical Navigable Small World graph. An approximate index, so its results
+//! are compared on recall as well as speed.
+
+use ::hnsw_rs::prelude::{DistL2, Distance, Hnsw};
+
+use crate::adapters::{ef_search, Item, KdTreeAdapter, Neighbour, Operation, EF_CONSTRUCTION};
+use crate::scalar::Scalar;
+
+/// Maximum number of links per node, above layer 0
+const MAX_CONNECTIONS: usize = 16;
+
+/// Maximum number of layers in the graph. hnsw_rs does not allow more than 16.
+const MAX_LAYERS: usize = 16;
+
+pub struct HnswRs<A: Clone + Send + Sync + 'static, const K: usize> {
+    graph: Hnsw<'static, A, DistL2>,
+    ef_search: usize,
+}
+
+impl<A, const K: usize> KdTreeAdapter<A, K> for HnswRs<A, K>
+where
+    A: Scalar,
+    DistL2: Distance<A>,
+{
+    const NAME: &'static str = "hnsw-rs";
+    const OPERATIONS: &'static [Operation] = &[Operation::NearestOne, Operation::NearestN];
+    const APPROXIMATE: bool = true;
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut graph = Hnsw::new(
+            MAX_CONNECTIONS,
+            points.len(),
+            MAX_LAYERS,
+            EF_CONSTRUCTION,
+            DistL2 {},
+        );
+
+        let data: Vec<(&[A], usize)> = points
+            .iter()
+            .enumerate()
+            .map(|(idx, point)| (&point[..], idx))
+            .collect();
+        graph.parallel_insert_slice(&data);
+        graph.set_searching_mode(true);
+
+        HnswRs {
+            graph,
+            ef_search: ef_search(),
+        }
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        self.nearest_n(query, 1)[0]
+    }
+
+    /// The candidate list can not be shorter than the number of results asked for
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        self.graph
+            .search(&query[..], qty, self.ef_search.max(qty))
+            .into_iter()
+            .map(|n| Neighbour {
+                distance: A::from_f64((n.distance as f64).powi(2)),
+                item: n.d_id as Item,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,84 @@
+//! instant-distance's HNSW graph. An approximate index, so its results are compared on
+//! recall as well as speed.
+
+use ::instant_distance::{Builder, HnswMap, Point, Search};
+
+use crate::adapters::{ef_search, Item, KdTreeAdapter, Neighbour, Operation, EF_CONSTRUCTION};
+use crate::dataset::DATASET_SEED;
+use crate::scalar::Scalar;
+
+/// instant-distance only works with `f32` distances, so points carry their own metric
+#[derive(Clone, Debug)]
+pub struct GraphPoint<A, const K: usize>([A; K]);
+
+impl<A: Scalar, const K: usize> GraphPoint<A, K> {
+    fn squared_distance(&self, other: &[A; K]) -> f64 {
+        self.0
+            .iter()
+            .zip(other)
+            .map(|(a, b)| (a.to_f64() - b.to_f64()).powi(2))
+            .sum()
+    }
+}
+
+/// Squared Euclidean, which orders candidates the same as Euclidean
+impl<A: Scalar, const K: usize> Point for GraphPoint<A, K> {
+    fn distance(&self, other: &Self) -> f32 {
+        self.squared_distance(&other.0) as f32
+    }
+}
+
+pub struct InstantDistance<A: Scalar, const K: usize> {
+    map: HnswMap<GraphPoint<A, K>, Item>,
+}
+
+impl<A: Scalar, const K: usize> KdTreeAdapter<A, K> for InstantDistance<A, K> {
+    const NAME: &'static str = "instant-distance";
+    const OPERATIONS: &'static [Operation] = &[Operation::NearestOne, Operation::NearestN];
+    const APPROXIMATE: bool = true;
+
+    type Input<'a> = (Vec<GraphPoint<A, K>>, Vec<Item>);
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        let graph_points = points.iter().map(|point| GraphPoint(*point)).collect();
+        let items = (0..points.len() as Item).collect();
+
+        (graph_points, items)
+    }
+
+    /// Seeded, so that repeated runs build the same graph
+    fn build((graph_points, items): Self::Input<'_>) -> Self {
+        InstantDistance {
+            map: Builder::default()
+                .ef_construction(EF_CONSTRUCTION)
+                .ef_search(ef_search())
+                .seed(DATASET_SEED)
+                .build(graph_points, items),
+        }
+    }
+
+    /// The candidate list size is fixed when the graph is built, and caps the results of
+    /// every query, unlike hnsw_rs's which is set per query
+    fn max_results() -> Option<usize> {
+        Some(ef_search())
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        self.nearest_n(query, 1)[0]
+    }
+
+    /// Distances are recalculated at full precision, rather than taken from the
+    /// `f32` ones that the graph was searched with
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        let mut search = Search::default();
+
+        self.map
+            .search(&GraphPoint(*query), &mut search)
+            .take(qty)
+            .map(|result| Neighbour {
+                distance: A::from_f64(result.point.squared_distance(query)),
+                item: *result.value,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,79 @@
+//! The `kd-tree` crate, an implicit tree over a sorted array of points.
+
+use ::kd_tree::ItemAndDistance;
+use num_traits::{Float, NumAssign};
+
+use crate::adapters::{Item, KdTreeAdapter, Neighbour, Operation};
+use crate::scalar::Scalar;
+
+pub struct KdTree<A, const K: usize> {
+    tree: ::kd_tree::KdTree<([A; K], Item)>,
+}
+
+fn to_neighbour<A, const K: usize>(result: ItemAndDistance<'_, ([A; K], Item), A>) -> Neighbour<A> {
+    Neighbour {
+        distance: result.squared_distance,
+        item: result.item.1,
+    }
+}
+
+impl<A, const K: usize> KdTreeAdapter<A, K> for KdTree<A, K>
+where
+    A: Scalar + Float + NumAssign,
+{
+    const NAME: &'static str = "kd-tree";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+    ];
+
+    type Input<'a> = Vec<([A; K], Item)>;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+            .iter()
+            .enumerate()
+            .map(|(idx, point)| (*point, idx as Item))
+            .collect()
+    }
+
+    fn build(items: Self::Input<'_>) -> Self {
+        KdTree {
+            tree: ::kd_tree::KdTree::build_by_ordered_float(items),
+        }
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        to_neighbour(self.tree.nearest(query).unwrap())
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        self.tree
+            .nearests(query, qty)
+            .into_iter()
+            .map(to_neighbour)
+            .collect()
+    }
+
+    /// kd-tree takes a radius that is not squared, and does not return distances or sort
+    /// its results, so both are done here to match the other libraries
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        let mut results: Vec<Neighbour<A>> = self
+            .tree
+            .within_radius(query, radius.sqrt())
+            .into_iter()
+            .map(|(point, item)| Neighbour {
+                distance: point
+                    .iter()
+                    .zip(query)
+                    .map(|(&a, &b)| (a - b) * (a - b))
+                    .fold(A::zero(), |acc, d| acc + d),
+                item: *item,
+            })
+            .collect();
+        results.sort_unstable_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+
+        results
+    }
+}
@@ -0,0 +1,73 @@
+//! The `kdtree` crate, which kiddo v1 was forked from.
+
+use ::kdtree::distance::squared_euclidean;
+use ::kdtree::KdTree;
+use num_traits::Float;
+
+use crate::adapters::{leaf_size, Item, KdTreeAdapter, LeafSize, Neighbour, Operation};
+use crate::scalar::Scalar;
+
+pub struct Kdtree<A: Float, const K: usize> {
+    tree: KdTree<A, Item, [A; K]>,
+}
+
+impl<A: Float, const K: usize> Kdtree<A, K> {
+    fn to_neighbours(results: Vec<(A, &Item)>) -> Vec<Neighbour<A>> {
+        results
+            .into_iter()
+            .map(|(distance, &item)| Neighbour { distance, item })
+            .collect()
+    }
+}
+
+impl<A: Scalar + Float, const K: usize> KdTreeAdapter<A, K> for Kdtree<A, K> {
+    const NAME: &'static str = "kdtree";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::Add,
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Runtime;
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut tree = Self::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            tree.add(point, idx as Item);
+        }
+
+        tree
+    }
+
+    /// kdtree's capacity is that of each leaf, rather than of the whole tree
+    fn with_capacity(_capacity: usize) -> Self {
+        Kdtree {
+            tree: KdTree::with_capacity(K, leaf_size()),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.tree.add(*point, item).unwrap();
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        self.nearest_n(query, 1)[0]
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        Self::to_neighbours(self.tree.nearest(query, qty, &squared_euclidean).unwrap())
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        Self::to_neighbours(self.tree.within(query, radius, &squared_euclidean).unwrap())
+    }
+}
@@ -0,0 +1,216 @@
+//! The in-development version of kiddo, built from the checkout at `../kiddo`.
+//!
+//! Its API tracks kiddo v5. Ids use `Kiddo_next` in place of `Kiddo_v5`, so that its
+//! results plot next to the released trees. With the `kiddo_next_mveb` feature, the
+//! immutable tree uses the modified van Emde Boas layout and its id says so.
+
+use std::num::NonZero;
+
+use ::kiddo_next::float::distance::SquaredEuclidean;
+use ::kiddo_next::float::kdtree::{Axis, KdTree};
+use ::kiddo_next::float_leaf_simd::leaf_node::BestFromDists;
+use ::kiddo_next::float_leaf_slice::leaf_slice::{LeafSliceFloat, LeafSliceFloatChunk};
+use ::kiddo_next::immutable::float::kdtree::ImmutableKdTree;
+use ::kiddo_next::nearest_neighbour::NearestNeighbour;
+
+use crate::adapters::{Item, KdTreeAdapter, LeafSize, Neighbour, Operation, BUCKET_SIZE};
+use crate::scalar::Scalar;
+
+fn to_neighbours<A>(results: Vec<NearestNeighbour<A, Item>>) -> Vec<Neighbour<A>> {
+    results
+        .into_iter()
+        .map(|n| Neighbour {
+            distance: n.distance,
+            item: n.item,
+        })
+        .collect()
+}
+
+pub struct KiddoNext<A: Axis, const K: usize, const B: usize = BUCKET_SIZE> {
+    tree: KdTree<A, Item, K, B, u32>,
+}
+
+impl<A, const K: usize, const B: usize> KdTreeAdapter<A, K> for KiddoNext<A, K, B>
+where
+    A: Axis + Scalar + BestFromDists<Item, B>,
+{
+    const NAME: &'static str = "Kiddo_next_std";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::Add,
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+        Operation::NearestNWithin,
+        Operation::BestNWithin,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Const(B);
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut tree = Self::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            tree.add(point, idx as Item);
+        }
+
+        tree
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        KiddoNext {
+            tree: KdTree::with_capacity(capacity),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.tree.add(point, item);
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let result = self.tree.nearest_one::<SquaredEuclidean>(query);
+
+        Neighbour {
+            distance: result.distance,
+            item: result.item,
+        }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        let Some(qty) = NonZero::new(qty) else {
+            return Vec::new();
+        };
+
+        to_neighbours(self.tree.nearest_n::<SquaredEuclidean>(query, qty))
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within::<SquaredEuclidean>(query, radius))
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within_unsorted::<SquaredEuclidean>(query, radius))
+    }
+
+    fn nearest_n_within(
+        &self,
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        sorted: bool,
+    ) -> Vec<Neighbour<A>> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        to_neighbours(
+            self.tree
+                .nearest_n_within::<SquaredEuclidean>(query, radius, max_qty, sorted),
+        )
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        self.tree
+            .best_n_within::<SquaredEuclidean>(query, radius, max_qty)
+            .map(|n| n.item)
+            .collect()
+    }
+}
+
+pub struct KiddoNextImmutable<A: Axis, const K: usize, const B: usize = BUCKET_SIZE> {
+    tree: ImmutableKdTree<A, Item, K, B>,
+}
+
+impl<A, const K: usize, const B: usize> KdTreeAdapter<A, K> for KiddoNextImmutable<A, K, B>
+where
+    A: Axis + Scalar + LeafSliceFloat<Item> + LeafSliceFloatChunk<Item, K>,
+{
+    #[cfg(not(feature = "kiddo_next_mveb"))]
+    const NAME: &'static str = "Kiddo_next_immutable";
+    #[cfg(feature = "kiddo_next_mveb")]
+    const NAME: &'static str = "Kiddo_next_immutable_mveb";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+        Operation::NearestNWithin,
+        Operation::BestNWithin,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Const(B);
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        KiddoNextImmutable {
+            tree: ImmutableKdTree::new_from_slice(points),
+        }
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let result = self.tree.nearest_one::<SquaredEuclidean>(query);
+
+        Neighbour {
+            distance: result.distance,
+            item: result.item,
+        }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        self.nearest_n_within(query, A::infinity(), qty, true)
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        self.nearest_n_within(query, radius, usize::MAX, true)
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        self.nearest_n_within(query, radius, usize::MAX, false)
+    }
+
+    fn nearest_n_within(
+        &self,
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        sorted: bool,
+    ) -> Vec<Neighbour<A>> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        to_neighbours(
+            self.tree
+                .nearest_n_within::<SquaredEuclidean>(query, radius, max_qty, sorted),
+        )
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        self.tree
+            .best_n_within::<SquaredEuclidean>(query, radius, max_qty)
+            .map(|n| n.item)
+            .collect()
+    }
+}
@@ -0,0 +1,93 @@
+use ::kiddo_v1::distance::squared_euclidean;
+use ::kiddo_v1::KdTree;
+use num_traits::Float;
+
+use crate::adapters::{leaf_size, Item, KdTreeAdapter, LeafSize, Neighbour, Operation};
+use crate::scalar::Scalar;
+
+pub struct KiddoV1<A: Float, const K: usize> {
+    tree: KdTree<A, Item, K>,
+}
+
+impl<A: Float, const K: usize> KiddoV1<A, K> {
+    fn to_neighbours(results: Vec<(A, &Item)>) -> Vec<Neighbour<A>> {
+        results
+            .into_iter()
+            .map(|(distance, &item)| Neighbour { distance, item })
+            .collect()
+    }
+}
+
+impl<A: Scalar + Float, const K: usize> KdTreeAdapter<A, K> for KiddoV1<A, K> {
+    const NAME: &'static str = "Kiddo_v1";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::Add,
+        Operation::Remove,
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+        Operation::BestNWithin,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Runtime;
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut tree = Self::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            tree.add(point, idx as Item);
+        }
+
+        tree
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        KiddoV1 {
+            tree: KdTree::with_per_node_capacity(leaf_size()).unwrap(),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.tree.add(point, item).unwrap();
+    }
+
+    fn remove(&mut self, point: &[A; K], item: Item) -> usize {
+        self.tree.remove(point, &item).unwrap()
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let (distance, &item) = self.tree.nearest_one(query, &squared_euclidean).unwrap();
+
+        Neighbour { distance, item }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        Self::to_neighbours(self.tree.nearest(query, qty, &squared_euclidean).unwrap())
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        Self::to_neighbours(self.tree.within(query, radius, &squared_euclidean).unwrap())
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        Self::to_neighbours(
+            self.tree
+                .within_unsorted(query, radius, &squared_euclidean)
+                .unwrap(),
+        )
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        self.tree
+            .best_n_within(query, radius, max_qty, &squared_euclidean)
+            .unwrap()
+    }
+}
@@ -0,0 +1,167 @@
+use ::kiddo_v2::fixed::distance::squared_euclidean as squared_euclidean_fixed;
+use ::kiddo_v2::fixed::kdtree::{Axis as AxisFixed, KdTree as FixedKdTree};
+use ::kiddo_v2::float::distance::squared_euclidean;
+use ::kiddo_v2::float::kdtree::{Axis, KdTree};
+use ::kiddo_v2::neighbour::Neighbour as KiddoNeighbour;
+
+use crate::adapters::{Item, KdTreeAdapter, LeafSize, Neighbour, Operation, BUCKET_SIZE};
+use crate::scalar::Scalar;
+
+const OPERATIONS: &[Operation] = &[
+    Operation::Add,
+    Operation::Remove,
+    Operation::NearestOne,
+    Operation::NearestN,
+    Operation::Within,
+    Operation::WithinUnsorted,
+    Operation::BestNWithin,
+];
+
+fn to_neighbours<A>(results: Vec<KiddoNeighbour<A, Item>>) -> Vec<Neighbour<A>> {
+    results
+        .into_iter()
+        .map(|n| Neighbour {
+            distance: n.distance,
+            item: n.item,
+        })
+        .collect()
+}
+
+pub struct KiddoV2<A: Axis, const K: usize, const B: usize = BUCKET_SIZE> {
+    tree: KdTree<A, Item, K, B, u32>,
+}
+
+impl<A: Axis + Scalar, const K: usize, const B: usize> KdTreeAdapter<A, K> for KiddoV2<A, K, B> {
+    const NAME: &'static str = "Kiddo_v2";
+    const OPERATIONS: &'static [Operation] = OPERATIONS;
+    const LEAF_SIZE: LeafSize = LeafSize::Const(B);
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut tree = Self::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            tree.add(point, idx as Item);
+        }
+
+        tree
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        KiddoV2 {
+            tree: KdTree::with_capacity(capacity),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.tree.add(point, item);
+    }
+
+    fn remove(&mut self, point: &[A; K], item: Item) -> usize {
+        self.tree.remove(point, item)
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let (distance, item) = self.tree.nearest_one(query, &squared_euclidean);
+
+        Neighbour { distance, item }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.nearest_n(query, qty, &squared_euclidean))
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within(query, radius, &squared_euclidean))
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within_unsorted(query, radius, &squared_euclidean))
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        self.tree
+            .best_n_within(query, radius, max_qty, &squared_euclidean)
+            .map(|n| n.item)
+            .collect()
+    }
+}
+
+pub struct KiddoV2Fixed<A: AxisFixed, const K: usize, const B: usize = BUCKET_SIZE> {
+    tree: FixedKdTree<A, Item, K, B, u32>,
+}
+
+impl<A: AxisFixed + Scalar, const K: usize, const B: usize> KdTreeAdapter<A, K>
+    for KiddoV2Fixed<A, K, B>
+{
+    const NAME: &'static str = "Kiddo_v2";
+    const OPERATIONS: &'static [Operation] = OPERATIONS;
+    const LEAF_SIZE: LeafSize = LeafSize::Const(B);
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut tree = Self::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            tree.add(point, idx as Item);
+        }
+
+        tree
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        KiddoV2Fixed {
+            tree: FixedKdTree::with_capacity(capacity),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.tree.add(point, item);
+    }
+
+    fn remove(&mut self, point: &[A; K], item: Item) -> usize {
+        self.tree.remove(point, item)
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let (distance, item) = self.tree.nearest_one(query, &squared_euclidean_fixed);
+
+        Neighbour { distance, item }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.nearest_n(query, qty, &squared_euclidean_fixed))
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within(query, radius, &squared_euclidean_fixed))
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(
+            self.tree
+                .within_unsorted(query, radius, &squared_euclidean_fixed),
+        )
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        self.tree
+            .best_n_within(query, radius, max_qty, &squared_euclidean_fixed)
+            .map(|n| n.item)
+            .collect()
+    }
+}
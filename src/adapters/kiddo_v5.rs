@@ -0,0 +1,305 @@
+use std::num::NonZero;
+
+use ::kiddo_v5::fixed::distance::SquaredEuclidean as SquaredEuclideanFixed;
+use ::kiddo_v5::fixed::kdtree::{Axis as AxisFixed, KdTree as FixedKdTree};
+use ::kiddo_v5::float::distance::SquaredEuclidean;
+use ::kiddo_v5::float::kdtree::{Axis, KdTree};
+use ::kiddo_v5::float_leaf_simd::leaf_node::BestFromDists;
+use ::kiddo_v5::float_leaf_slice::leaf_slice::{LeafSliceFloat, LeafSliceFloatChunk};
+use ::kiddo_v5::immutable::float::kdtree::ImmutableKdTree;
+use ::kiddo_v5::nearest_neighbour::NearestNeighbour;
+
+use crate::adapters::{Item, KdTreeAdapter, LeafSize, Neighbour, Operation, BUCKET_SIZE};
+use crate::scalar::Scalar;
+
+fn to_neighbours<A>(results: Vec<NearestNeighbour<A, Item>>) -> Vec<Neighbour<A>> {
+    results
+        .into_iter()
+        .map(|n| Neighbour {
+            distance: n.distance,
+            item: n.item,
+        })
+        .collect()
+}
+
+pub struct KiddoV5<A: Axis, const K: usize, const B: usize = BUCKET_SIZE> {
+    tree: KdTree<A, Item, K, B, u32>,
+}
+
+impl<A, const K: usize, const B: usize> KdTreeAdapter<A, K> for KiddoV5<A, K, B>
+where
+    A: Axis + Scalar + BestFromDists<Item, B>,
+{
+    const NAME: &'static str = "Kiddo_v5_std";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::Add,
+        Operation::Remove,
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+        Operation::NearestNWithin,
+        Operation::BestNWithin,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Const(B);
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut tree = Self::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            tree.add(point, idx as Item);
+        }
+
+        tree
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        KiddoV5 {
+            tree: KdTree::with_capacity(capacity),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.tree.add(point, item);
+    }
+
+    fn remove(&mut self, point: &[A; K], item: Item) -> usize {
+        self.tree.remove(point, item)
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let result = self.tree.nearest_one::<SquaredEuclidean>(query);
+
+        Neighbour {
+            distance: result.distance,
+            item: result.item,
+        }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        let Some(qty) = NonZero::new(qty) else {
+            return Vec::new();
+        };
+
+        to_neighbours(self.tree.nearest_n::<SquaredEuclidean>(query, qty))
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within::<SquaredEuclidean>(query, radius))
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within_unsorted::<SquaredEuclidean>(query, radius))
+    }
+
+    fn nearest_n_within(
+        &self,
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        sorted: bool,
+    ) -> Vec<Neighbour<A>> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        to_neighbours(
+            self.tree
+                .nearest_n_within::<SquaredEuclidean>(query, radius, max_qty, sorted),
+        )
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        self.tree
+            .best_n_within::<SquaredEuclidean>(query, radius, max_qty)
+            .map(|n| n.item)
+            .collect()
+    }
+}
+
+pub struct KiddoV5Fixed<A: AxisFixed, const K: usize, const B: usize = BUCKET_SIZE> {
+    tree: FixedKdTree<A, Item, K, B, u32>,
+}
+
+impl<A: AxisFixed + Scalar, const K: usize, const B: usize> KdTreeAdapter<A, K>
+    for KiddoV5Fixed<A, K, B>
+{
+    const NAME: &'static str = "Kiddo_v5_std";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::Add,
+        Operation::Remove,
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+        Operation::BestNWithin,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Const(B);
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        let mut tree = Self::with_capacity(points.len());
+        for (idx, point) in points.iter().enumerate() {
+            tree.add(point, idx as Item);
+        }
+
+        tree
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        KiddoV5Fixed {
+            tree: FixedKdTree::with_capacity(capacity),
+        }
+    }
+
+    fn add(&mut self, point: &[A; K], item: Item) {
+        self.tree.add(point, item);
+    }
+
+    fn remove(&mut self, point: &[A; K], item: Item) -> usize {
+        self.tree.remove(point, item)
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let result = self.tree.nearest_one::<SquaredEuclideanFixed>(query);
+
+        Neighbour {
+            distance: result.distance,
+            item: result.item,
+        }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        let Some(qty) = NonZero::new(qty) else {
+            return Vec::new();
+        };
+
+        to_neighbours(self.tree.nearest_n::<SquaredEuclideanFixed>(query, qty))
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(self.tree.within::<SquaredEuclideanFixed>(query, radius))
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        to_neighbours(
+            self.tree
+                .within_unsorted::<SquaredEuclideanFixed>(query, radius),
+        )
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        self.tree
+            .best_n_within::<SquaredEuclideanFixed>(query, radius, max_qty)
+            .map(|n| n.item)
+            .collect()
+    }
+}
+
+pub struct KiddoV5Immutable<A: Axis, const K: usize, const B: usize = BUCKET_SIZE> {
+    tree: ImmutableKdTree<A, Item, K, B>,
+}
+
+impl<A, const K: usize, const B: usize> KdTreeAdapter<A, K> for KiddoV5Immutable<A, K, B>
+where
+    A: Axis + Scalar + LeafSliceFloat<Item> + LeafSliceFloatChunk<Item, K>,
+{
+    const NAME: &'static str = "Kiddo_v5_immutable";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+        Operation::NearestNWithin,
+        Operation::BestNWithin,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Const(B);
+
+    type Input<'a>
+        = &'a [[A; K]]
+    where
+        A: 'a;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        KiddoV5Immutable {
+            tree: ImmutableKdTree::new_from_slice(points),
+        }
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let result = self.tree.nearest_one::<SquaredEuclidean>(query);
+
+        Neighbour {
+            distance: result.distance,
+            item: result.item,
+        }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        self.nearest_n_within(query, A::infinity(), qty, true)
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        self.nearest_n_within(query, radius, usize::MAX, true)
+    }
+
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        self.nearest_n_within(query, radius, usize::MAX, false)
+    }
+
+    fn nearest_n_within(
+        &self,
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        sorted: bool,
+    ) -> Vec<Neighbour<A>> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        to_neighbours(
+            self.tree
+                .nearest_n_within::<SquaredEuclidean>(query, radius, max_qty, sorted),
+        )
+    }
+
+    fn best_n_within(&self, query: &[A; K], radius: A, max_qty: usize) -> Vec<Item> {
+        let Some(max_qty) = NonZero::new(max_qty) else {
+            return Vec::new();
+        };
+
+        self.tree
+            .best_n_within::<SquaredEuclidean>(query, radius, max_qty)
+            .map(|n| n.item)
+            .collect()
+    }
+}
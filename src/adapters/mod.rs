@@ -0,0 +1,314 @@
+//! A common interface over each of the k-d tree libraries being compared.
+//!
+//! Scenarios are written once against [`KdTreeAdapter`], and each library only
+//! needs an impl of it to take part in every scenario that it supports.
+//!
+//! Each library's adapter is only compiled when the cargo feature of the same name is enabled,
+//! apart from the in-crate [`BruteForce`] baseline, which is always available.
+//!
+//! Adapters that set [`KdTreeAdapter::APPROXIMATE`] wrap approximate indexes, whose speed
+//! depends on the size of their search candidate list. That is set with [`set_ef_search`].
+//!
+//! Adapters whose library exposes a leaf (bucket) size say so with [`KdTreeAdapter::LEAF_SIZE`].
+//! Libraries that take it at runtime are built with [`leaf_size`], set with [`set_leaf_size`].
+//! kiddo takes it as a const generic, so its adapters have a `B` parameter instead.
+
+use std::env;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::scalar::Scalar;
+
+pub mod brute_force;
+
+#[cfg(feature = "fnntw")]
+pub mod fnntw;
+#[cfg(feature = "hnsw_rs")]
+pub mod hnsw_rs;
+#[cfg(feature = "instant-distance")]
+pub mod instant_distance;
+#[cfg(feature = "kd-tree")]
+pub mod kd_tree;
+#[cfg(feature = "kdtree")]
+pub mod kdtree;
+#[cfg(feature = "kiddo_next")]
+pub mod kiddo_next;
+#[cfg(feature = "kiddo_v1")]
+pub mod kiddo_v1;
+#[cfg(feature = "kiddo_v2")]
+pub mod kiddo_v2;
+#[cfg(feature = "kiddo_v3")]
+pub mod kiddo_v3;
+#[cfg(feature = "kiddo_v5")]
+pub mod kiddo_v5;
+#[cfg(feature = "nabo")]
+pub mod nabo;
+#[cfg(feature = "nabo")]
+pub mod nabo_points;
+#[cfg(feature = "petal-neighbors")]
+pub mod petal_neighbors;
+#[cfg(feature = "rstar")]
+pub mod rstar;
+
+pub use self::brute_force::BruteForce;
+#[cfg(feature = "fnntw")]
+pub use self::fnntw::Fnntw;
+#[cfg(feature = "hnsw_rs")]
+pub use self::hnsw_rs::HnswRs;
+#[cfg(feature = "instant-distance")]
+pub use self::instant_distance::InstantDistance;
+#[cfg(feature = "kd-tree")]
+pub use self::kd_tree::KdTree;
+#[cfg(feature = "kdtree")]
+pub use self::kdtree::Kdtree;
+#[cfg(feature = "kiddo_next")]
+pub use self::kiddo_next::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v1")]
+pub use self::kiddo_v1::KiddoV1;
+#[cfg(feature = "kiddo_v2")]
+pub use self::kiddo_v2::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+pub use self::kiddo_v3::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+pub use self::kiddo_v5::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+#[cfg(feature = "nabo")]
+pub use self::nabo::Nabo;
+#[cfg(feature = "petal-neighbors")]
+pub use self::petal_neighbors::PetalBallTree;
+#[cfg(feature = "rstar")]
+pub use self::rstar::Rstar;
+
+/// Leaf size that trees are built with, unless overridden. Used by every library that
+/// exposes one, rather than each library's own default, so that they are compared like-for-like.
+pub const BUCKET_SIZE: usize = 32;
+
+/// Environment variable that overrides [`BUCKET_SIZE`] for the libraries that take their leaf
+/// size at runtime, including the Python ones
+pub const LEAF_SIZE_ENV: &str = "KD_TREE_LEAF_SIZE";
+
+static RUNTIME_LEAF_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+/// Leaf size that libraries which take it at runtime are built with. Read when a tree is
+/// built, so changing it does not affect trees that already exist.
+pub fn leaf_size() -> usize {
+    match RUNTIME_LEAF_SIZE.load(Ordering::Relaxed) {
+        0 => env::var(LEAF_SIZE_ENV)
+            .ok()
+            .and_then(|leaf_size| leaf_size.parse().ok())
+            .unwrap_or(BUCKET_SIZE),
+        leaf_size => leaf_size,
+    }
+}
+
+pub fn set_leaf_size(leaf_size: usize) {
+    RUNTIME_LEAF_SIZE.store(leaf_size, Ordering::Relaxed);
+}
+
+static TAG_LEAF_SIZES: AtomicBool = AtomicBool::new(false);
+
+/// Whether every leaf size is tagged in benchmark ids, [`BUCKET_SIZE`] included. Set for
+/// leaf size sweeps, so that each leaf size has an id of its own.
+pub fn set_tag_leaf_sizes(tag: bool) {
+    TAG_LEAF_SIZES.store(tag, Ordering::Relaxed);
+}
+
+/// ` leaf=N` when leaf sizes are being swept, or when the leaf size is not [`BUCKET_SIZE`].
+/// Otherwise nothing, so that ids match those of results recorded before leaf sizes were tagged.
+pub fn leaf_size_tag(leaf_size: usize) -> String {
+    if TAG_LEAF_SIZES.load(Ordering::Relaxed) || leaf_size != BUCKET_SIZE {
+        format!(" leaf={}", leaf_size)
+    } else {
+        String::new()
+    }
+}
+
+/// How an adapter's library sets the number of points in each leaf of its tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeafSize {
+    /// The library does not have leaves, or does not let their size be chosen
+    NotExposed,
+
+    /// Fixed at compile time, as with kiddo's bucket size
+    Const(usize),
+
+    /// Taken from [`leaf_size`] when a tree is built
+    Runtime,
+}
+
+impl LeafSize {
+    /// The leaf size that a tree built now would have, if the library exposes one
+    pub fn get(&self) -> Option<usize> {
+        match self {
+            LeafSize::NotExposed => None,
+            LeafSize::Const(leaf_size) => Some(*leaf_size),
+            LeafSize::Runtime => Some(leaf_size()),
+        }
+    }
+}
+
+/// Candidate list size used while building the approximate indexes
+pub const EF_CONSTRUCTION: usize = 200;
+
+/// Candidate list size used when querying the approximate indexes, unless overridden
+pub const DEFAULT_EF_SEARCH: usize = 64;
+
+/// Environment variable that overrides [`DEFAULT_EF_SEARCH`] for the benches
+pub const EF_SEARCH_ENV: &str = "KD_TREE_EF_SEARCH";
+
+static EF_SEARCH: AtomicUsize = AtomicUsize::new(0);
+
+/// Candidate list size that approximate indexes are searched with. Read when a tree is
+/// built, so changing it does not affect trees that already exist.
+pub fn ef_search() -> usize {
+    match EF_SEARCH.load(Ordering::Relaxed) {
+        0 => env::var(EF_SEARCH_ENV)
+            .ok()
+            .and_then(|ef| ef.parse().ok())
+            .unwrap_or(DEFAULT_EF_SEARCH),
+        ef => ef,
+    }
+}
+
+pub fn set_ef_search(ef: usize) {
+    EF_SEARCH.store(ef, Ordering::Relaxed);
+}
+
+/// Tags that distinguish runs of the same adapter in benchmark ids, eg ` leaf=64` for
+/// libraries built with a leaf size other than the default, and ` ef=64` for approximate indexes
+pub fn adapter_tags<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>() -> String {
+    let mut tags = String::new();
+    if let Some(leaf_size) = T::LEAF_SIZE.get() {
+        tags.push_str(&leaf_size_tag(leaf_size));
+    }
+    if T::APPROXIMATE {
+        tags.push_str(&format!(" ef={}", ef_search()));
+    }
+
+    tags
+}
+
+/// Item stored against each point. Trees built with [`KdTreeAdapter::build_from_slice`]
+/// store each point's index in the source slice.
+pub type Item = u32;
+
+/// A single query result. Distances are always squared Euclidean.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Neighbour<A> {
+    pub distance: A,
+    pub item: Item,
+}
+
+/// The operations that an adapter can support, beyond construction from a slice
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Add,
+    Remove,
+    NearestOne,
+    NearestN,
+    Within,
+    WithinUnsorted,
+    NearestNWithin,
+    BestNWithin,
+}
+
+/// Wraps a k-d tree library so that it can be driven by the generic scenarios.
+///
+/// All radii and returned distances are squared Euclidean distances, whatever the
+/// native convention of the underlying library. Methods for operations not listed in
+/// [`KdTreeAdapter::OPERATIONS`] panic.
+pub trait KdTreeAdapter<A: Scalar, const K: usize>: Sized + Sync {
+    /// Library name as it appears in benchmark ids, eg `Kiddo_v5_immutable`. `_` separates
+    /// the library from its version and variant, so names of more than one word use `-`,
+    /// eg `brute-force`.
+    const NAME: &'static str;
+
+    /// Operations supported by this adapter
+    const OPERATIONS: &'static [Operation];
+
+    /// Whether query results can miss some of the true neighbours
+    const APPROXIMATE: bool = false;
+
+    /// How the tree's leaf size is set, if the library exposes one
+    const LEAF_SIZE: LeafSize = LeafSize::NotExposed;
+
+    /// The library's own form of the points that a tree is built from, or the points
+    /// themselves if it takes them as they are
+    type Input<'a>
+    where
+        A: 'a;
+
+    fn supports(op: Operation) -> bool {
+        Self::OPERATIONS.contains(&op)
+    }
+
+    /// Most results that one query can return, if the library caps it, eg at the candidate
+    /// list size that an approximate index was built with. Queries for more are skipped.
+    fn max_results() -> Option<usize> {
+        None
+    }
+
+    /// Whether a query for `qty` results is within [`KdTreeAdapter::max_results`]
+    fn supports_qty(qty: usize) -> bool {
+        Self::max_results().is_none_or(|max| qty <= max)
+    }
+
+    /// Converts points into the library's own form, with each point's index as its item.
+    /// Kept apart from [`KdTreeAdapter::build`] so that construction timings leave it out.
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_>;
+
+    /// Builds a tree from points already [prepared](KdTreeAdapter::prepare)
+    fn build(input: Self::Input<'_>) -> Self;
+
+    /// Builds a tree containing every point in `points`, with each point's index as its item
+    fn build_from_slice(points: &[[A; K]]) -> Self {
+        Self::build(Self::prepare(points))
+    }
+
+    /// Creates an empty tree that points can be [added](KdTreeAdapter::add) to
+    fn with_capacity(_capacity: usize) -> Self {
+        unsupported::<Self, A, K>(Operation::Add)
+    }
+
+    fn add(&mut self, _point: &[A; K], _item: Item) {
+        unsupported::<Self, A, K>(Operation::Add)
+    }
+
+    /// Removes every entry of `item` at `point`, returning how many were removed
+    fn remove(&mut self, _point: &[A; K], _item: Item) -> usize {
+        unsupported::<Self, A, K>(Operation::Remove)
+    }
+
+    fn nearest_one(&self, _query: &[A; K]) -> Neighbour<A> {
+        unsupported::<Self, A, K>(Operation::NearestOne)
+    }
+
+    fn nearest_n(&self, _query: &[A; K], _qty: usize) -> Vec<Neighbour<A>> {
+        unsupported::<Self, A, K>(Operation::NearestN)
+    }
+
+    fn within(&self, _query: &[A; K], _radius: A) -> Vec<Neighbour<A>> {
+        unsupported::<Self, A, K>(Operation::Within)
+    }
+
+    fn within_unsorted(&self, _query: &[A; K], _radius: A) -> Vec<Neighbour<A>> {
+        unsupported::<Self, A, K>(Operation::WithinUnsorted)
+    }
+
+    fn nearest_n_within(
+        &self,
+        _query: &[A; K],
+        _radius: A,
+        _max_qty: usize,
+        _sorted: bool,
+    ) -> Vec<Neighbour<A>> {
+        unsupported::<Self, A, K>(Operation::NearestNWithin)
+    }
+
+    /// Returns up to `max_qty` of the lowest-valued items within `radius` of `query`
+    fn best_n_within(&self, _query: &[A; K], _radius: A, _max_qty: usize) -> Vec<Item> {
+        unsupported::<Self, A, K>(Operation::BestNWithin)
+    }
+}
+
+fn unsupported<T: KdTreeAdapter<A, K>, A: Scalar, const K: usize>(op: Operation) -> ! {
+    panic!("{} does not support {:?}", T::NAME, op)
+}
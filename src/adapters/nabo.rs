@@ -0,0 +1,88 @@
+use std::fmt::Debug;
+use std::ops::{AddAssign, SubAssign};
+
+use ::nabo::{CandidateContainer, KDTree, Parameters};
+use num_traits::Float;
+
+use crate::adapters::nabo_points::{point_from_array, P};
+use crate::adapters::{leaf_size, Item, KdTreeAdapter, LeafSize, Neighbour, Operation};
+use crate::scalar::Scalar;
+
+pub struct Nabo<A: Float + Debug + Default + AddAssign + SubAssign, const K: usize> {
+    tree: KDTree<A, P<A, K>>,
+}
+
+impl<A, const K: usize> KdTreeAdapter<A, K> for Nabo<A, K>
+where
+    A: Scalar + Float + Debug + Default + AddAssign + SubAssign,
+{
+    const NAME: &'static str = "nabo";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::NearestNWithin,
+    ];
+    const LEAF_SIZE: LeafSize = LeafSize::Runtime;
+
+    type Input<'a> = Vec<P<A, K>>;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points.iter().map(point_from_array).collect()
+    }
+
+    fn build(cloud: Self::Input<'_>) -> Self {
+        Nabo {
+            tree: KDTree::new_with_bucket_size(&cloud, leaf_size() as u32),
+        }
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        self.nearest_n(query, 1)[0]
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        self.tree
+            .knn(qty as u32, &point_from_array(query))
+            .into_iter()
+            .map(|n| Neighbour {
+                distance: n.dist2.into_inner(),
+                item: n.index as Item,
+            })
+            .collect()
+    }
+
+    fn nearest_n_within(
+        &self,
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        sorted: bool,
+    ) -> Vec<Neighbour<A>> {
+        let params = Parameters {
+            epsilon: A::zero(),
+
+            // Nabo works with normalized radius, rather than dist according to the metric.
+            // We need to convert the radius so that it performs the same query as the
+            // other libraries being benchmarked
+            max_radius: radius.sqrt(),
+
+            allow_self_match: true,
+            sort_results: sorted,
+        };
+
+        self.tree
+            .knn_advanced(
+                max_qty.min(u32::MAX as usize) as u32,
+                &point_from_array(query),
+                CandidateContainer::BinaryHeap,
+                &params,
+                None,
+            )
+            .into_iter()
+            .map(|n| Neighbour {
+                distance: n.dist2.into_inner(),
+                item: n.index as Item,
+            })
+            .collect()
+    }
+}
@@ -64,7 +64,7 @@ impl<A: Float + Debug + Default + AddAssign + SubAssign, const K: usize> Sub for
     }
 }
 
-/// Creates a random point whose coordinate are in the interval [-100:100].
+/// Creates a random point whose coordinates are in the interval [0, 1).
 pub fn random_point<A: Float + Debug + Default + AddAssign + SubAssign, const K: usize>() -> P<A, K>
 where
     Standard: Distribution<[A; K]>,
@@ -88,3 +88,10 @@ where
 {
     (0..count).map(|_| random_point()).collect()
 }
+
+/// Converts a plain coordinate array into a nabo point.
+pub fn point_from_array<A: Float + Debug + Default + AddAssign + SubAssign, const K: usize>(
+    raw_point: &[A; K],
+) -> P<A, K> {
+    P(raw_point.map(|coord| NotNan::new(coord).unwrap()))
+}
@@ -0,0 +1,84 @@
+//! petal-neighbors' ball tree, the usual alternative to a k-d tree at higher dimensions.
+
+use std::ops::{AddAssign, DivAssign};
+
+use ::petal_neighbors::distance::Euclidean;
+use ::petal_neighbors::BallTree;
+use ndarray::{aview1, Array2};
+use num_traits::{Float, FromPrimitive};
+
+use crate::adapters::{Item, KdTreeAdapter, Neighbour, Operation};
+use crate::scalar::Scalar;
+
+pub struct PetalBallTree<A: Float, const K: usize> {
+    tree: BallTree<'static, A, Euclidean>,
+}
+
+impl<A, const K: usize> KdTreeAdapter<A, K> for PetalBallTree<A, K>
+where
+    A: Scalar + Float + FromPrimitive + AddAssign + DivAssign,
+{
+    const NAME: &'static str = "petal-balltree";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+    ];
+
+    type Input<'a> = Array2<A>;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        Array2::from(points.to_vec())
+    }
+
+    fn build(points: Self::Input<'_>) -> Self {
+        PetalBallTree {
+            tree: BallTree::euclidean(points).unwrap(),
+        }
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        let (idx, distance) = self.tree.query_nearest(&aview1(query));
+
+        Neighbour {
+            distance: distance * distance,
+            item: idx as Item,
+        }
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        let (indices, distances) = self.tree.query(&aview1(query), qty);
+
+        indices
+            .into_iter()
+            .zip(distances)
+            .map(|(idx, distance)| Neighbour {
+                distance: distance * distance,
+                item: idx as Item,
+            })
+            .collect()
+    }
+
+    /// petal-neighbors takes a radius that is not squared, and only returns the indices of
+    /// the points within it, unsorted, so their distances are calculated and sorted here
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        let points = self.tree.points.view();
+        let mut results: Vec<Neighbour<A>> = self
+            .tree
+            .query_radius(&aview1(query), radius.sqrt())
+            .into_iter()
+            .map(|idx| Neighbour {
+                distance: points
+                    .row(idx)
+                    .iter()
+                    .zip(query)
+                    .map(|(&a, &b)| (a - b) * (a - b))
+                    .fold(A::zero(), |acc, d| acc + d),
+                item: idx as Item,
+            })
+            .collect();
+        results.sort_unstable_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+
+        results
+    }
+}
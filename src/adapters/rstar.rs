@@ -0,0 +1,80 @@
+//! rstar's R*-tree, as the most common alternative to a k-d tree for spatial queries in Rust.
+
+use ::rstar::primitives::GeomWithData;
+use ::rstar::{Point, PointDistance, RTree, RTreeNum};
+
+use crate::adapters::{Item, KdTreeAdapter, Neighbour, Operation};
+use crate::scalar::Scalar;
+
+type Entry<A, const K: usize> = GeomWithData<[A; K], Item>;
+
+pub struct Rstar<A: RTreeNum, const K: usize>
+where
+    [A; K]: Point<Scalar = A>,
+{
+    tree: RTree<Entry<A, K>>,
+}
+
+impl<A, const K: usize> KdTreeAdapter<A, K> for Rstar<A, K>
+where
+    A: Scalar + RTreeNum,
+    [A; K]: Point<Scalar = A>,
+{
+    const NAME: &'static str = "rstar";
+    const OPERATIONS: &'static [Operation] = &[
+        Operation::NearestOne,
+        Operation::NearestN,
+        Operation::Within,
+        Operation::WithinUnsorted,
+    ];
+
+    /// Bulk loads the tree, which packs it far better than inserting points one at a time
+    type Input<'a> = Vec<Entry<A, K>>;
+
+    fn prepare(points: &[[A; K]]) -> Self::Input<'_> {
+        points
+            .iter()
+            .enumerate()
+            .map(|(idx, point)| GeomWithData::new(*point, idx as Item))
+            .collect()
+    }
+
+    fn build(entries: Self::Input<'_>) -> Self {
+        Rstar {
+            tree: RTree::bulk_load(entries),
+        }
+    }
+
+    fn nearest_one(&self, query: &[A; K]) -> Neighbour<A> {
+        self.nearest_n(query, 1)[0]
+    }
+
+    fn nearest_n(&self, query: &[A; K], qty: usize) -> Vec<Neighbour<A>> {
+        self.tree
+            .nearest_neighbor_iter_with_distance_2(query)
+            .take(qty)
+            .map(|(entry, distance)| Neighbour {
+                distance,
+                item: entry.data,
+            })
+            .collect()
+    }
+
+    fn within(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        let mut results = self.within_unsorted(query, radius);
+        results.sort_unstable_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap());
+
+        results
+    }
+
+    /// `locate_within_distance` takes a squared radius, like the other libraries
+    fn within_unsorted(&self, query: &[A; K], radius: A) -> Vec<Neighbour<A>> {
+        self.tree
+            .locate_within_distance(*query, radius)
+            .map(|entry| Neighbour {
+                distance: entry.distance_2(query),
+                item: entry.data,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,93 @@
+//! Named codegen profiles that the suite can be rebuilt under, to see how much of each
+//! library's performance depends on the CPU level it is compiled for, on LTO, or on PGO.
+//!
+//! Results of a profile are tagged `codegen=<profile>`, in the same way as
+//! [variants](crate::variants).
+
+use std::path::Path;
+
+use clap::ValueEnum;
+
+/// Key of the tag that records which codegen profile a result came from
+pub const CODEGEN_TAG: &str = "codegen";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CodegenProfile {
+    /// The x86-64 baseline, with SSE2 but nothing later
+    #[value(name = "x86-64")]
+    X8664,
+
+    /// x86-64 with SSE4.2 and POPCNT
+    #[value(name = "x86-64-v2")]
+    X8664V2,
+
+    /// x86-64 with AVX2, BMI2 and FMA
+    #[value(name = "x86-64-v3")]
+    X8664V3,
+
+    /// Every feature of the CPU that the suite is built on
+    Native,
+
+    /// Fat LTO with a single codegen unit, for the default target CPU
+    FatLto,
+
+    /// Profile-guided optimisation, trained on a run of the same benchmarks
+    Pgo,
+}
+
+impl CodegenProfile {
+    /// Name of the profile as it appears in benchmark ids
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodegenProfile::X8664 => "x86-64",
+            CodegenProfile::X8664V2 => "x86-64-v2",
+            CodegenProfile::X8664V3 => "x86-64-v3",
+            CodegenProfile::Native => "native",
+            CodegenProfile::FatLto => "fat-lto",
+            CodegenProfile::Pgo => "pgo",
+        }
+    }
+
+    /// Flags to pass to rustc through `RUSTFLAGS`
+    pub fn rustflags(&self) -> Vec<String> {
+        match self {
+            CodegenProfile::X8664 => vec!["-Ctarget-cpu=x86-64".to_string()],
+            CodegenProfile::X8664V2 => vec!["-Ctarget-cpu=x86-64-v2".to_string()],
+            CodegenProfile::X8664V3 => vec!["-Ctarget-cpu=x86-64-v3".to_string()],
+            CodegenProfile::Native => vec!["-Ctarget-cpu=native".to_string()],
+            CodegenProfile::FatLto | CodegenProfile::Pgo => vec![],
+        }
+    }
+
+    /// Overrides of the cargo profiles that the benches and `kdbench` are built with
+    pub fn cargo_env(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            CodegenProfile::FatLto => vec![
+                ("CARGO_PROFILE_RELEASE_LTO", "fat"),
+                ("CARGO_PROFILE_RELEASE_CODEGEN_UNITS", "1"),
+                ("CARGO_PROFILE_BENCH_LTO", "fat"),
+                ("CARGO_PROFILE_BENCH_CODEGEN_UNITS", "1"),
+            ],
+            _ => vec![],
+        }
+    }
+
+    /// Whether the suite needs an instrumented training run before it is built for real
+    pub fn needs_training(&self) -> bool {
+        *self == CodegenProfile::Pgo
+    }
+}
+
+/// rustc flag for the instrumented build of a PGO profile, writing raw profiles to `dir`
+pub fn pgo_generate_flag(dir: &Path) -> String {
+    format!("-Cprofile-generate={}", dir.display())
+}
+
+/// rustc flags for the optimised build of a PGO profile, using the merged `profdata`.
+/// Functions that the training run never reached are not worth a warning each.
+pub fn pgo_use_flags(profdata: &Path) -> Vec<String> {
+    vec![
+        format!("-Cprofile-use={}", profdata.display()),
+        "-Cllvm-args=-pgo-warn-missing-function=false".to_string(),
+    ]
+}
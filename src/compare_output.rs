@@ -0,0 +1,483 @@
+//! Cross-library correctness checks.
+//!
+//! Every library's tree is built over the same seeded dataset and given the same queries.
+//! The results of each library are then compared against those of the first library
+//! that supports the query, and every disagreement is reported. This stops a library
+//! that returns wrong answers quickly from looking like a winner in the charts.
+//!
+//! For float types, the first library is the exact [`BruteForce`] scan, so every library
+//! is checked against the ground truth rather than against another tree.
+
+use std::fmt;
+
+use clap::Parser;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+
+#[cfg(feature = "fnntw")]
+use crate::adapters::Fnntw;
+#[cfg(feature = "kd-tree")]
+use crate::adapters::KdTree;
+#[cfg(feature = "kdtree")]
+use crate::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use crate::adapters::KiddoV1;
+#[cfg(feature = "nabo")]
+use crate::adapters::Nabo;
+#[cfg(feature = "petal-neighbors")]
+use crate::adapters::PetalBallTree;
+#[cfg(feature = "rstar")]
+use crate::adapters::Rstar;
+use crate::adapters::{BruteForce, Item, KdTreeAdapter, Neighbour, Operation};
+#[cfg(feature = "kiddo_next")]
+use crate::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v2")]
+use crate::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use crate::adapters::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use crate::adapters::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+use crate::distribution::Distribution;
+use crate::scalar::Scalar;
+#[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+use crate::scalar::FXP;
+use crate::scenarios::{nearest_n_within_max_results, BEST_N_RADIUS, RADIUS};
+use crate::utils::dims_and_type;
+
+/// Command line arguments shared by the `compare-output-*` binaries
+#[derive(Parser, Debug)]
+pub struct CompareArgs {
+    /// Number of points in each tree
+    #[arg(long, default_value_t = 100_000)]
+    pub size: usize,
+
+    /// Number of query points
+    #[arg(long, default_value_t = 1_000)]
+    pub queries: usize,
+
+    /// Seed for the dataset and query points
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+
+    /// Distribution that the dataset and query points are drawn from
+    #[arg(long, value_enum, default_value_t = Distribution::Uniform)]
+    pub distribution: Distribution,
+
+    /// Result count for nearest_n and best_n_within
+    #[arg(long, default_value_t = 10)]
+    pub k: usize,
+
+    /// Squared radius for the radius-based queries. Defaults to the radius used by the benches.
+    #[arg(long)]
+    pub radius: Option<f64>,
+
+    /// Maximum number of mismatches to print per comparison
+    #[arg(long, default_value_t = 20)]
+    pub max_reported: usize,
+}
+
+/// A query that is run against every library and compared
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Check {
+    NearestOne,
+    NearestN { qty: usize },
+    Within { radius: f64 },
+    NearestNWithin { radius: f64, max_qty: usize },
+    BestNWithin { radius: f64, max_qty: usize },
+}
+
+impl Check {
+    pub fn nearest_one() -> Self {
+        Check::NearestOne
+    }
+
+    pub fn nearest_n(args: &CompareArgs) -> Self {
+        Check::NearestN { qty: args.k }
+    }
+
+    pub fn within(args: &CompareArgs) -> Self {
+        Check::Within {
+            radius: args.radius.unwrap_or(RADIUS),
+        }
+    }
+
+    pub fn nearest_n_within(args: &CompareArgs) -> Self {
+        Check::NearestNWithin {
+            radius: args.radius.unwrap_or(RADIUS),
+            max_qty: nearest_n_within_max_results(args.size),
+        }
+    }
+
+    pub fn best_n_within(args: &CompareArgs) -> Self {
+        Check::BestNWithin {
+            radius: args.radius.unwrap_or(BEST_N_RADIUS),
+            max_qty: args.k,
+        }
+    }
+
+    fn operation(&self) -> Operation {
+        match self {
+            Check::NearestOne => Operation::NearestOne,
+            Check::NearestN { .. } => Operation::NearestN,
+            Check::Within { .. } => Operation::Within,
+            Check::NearestNWithin { .. } => Operation::NearestNWithin,
+            Check::BestNWithin { .. } => Operation::BestNWithin,
+        }
+    }
+}
+
+/// Points that every tree is built from, and the points that every tree is queried with
+pub struct Dataset<A: Scalar, const K: usize> {
+    pub points: Vec<[A; K]>,
+    pub queries: Vec<[A; K]>,
+}
+
+impl<A: Scalar, const K: usize> Dataset<A, K> {
+    pub fn seeded(size: usize, query_qty: usize, seed: u64, distribution: Distribution) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut sample = |qty| {
+            distribution
+                .sample::<_, K>(qty, &mut rng)
+                .into_iter()
+                .map(|point| point.map(A::from_f64))
+                .collect()
+        };
+
+        Dataset {
+            points: sample(size),
+            queries: sample(query_qty),
+        }
+    }
+
+    fn squared_distance(&self, query: &[A; K], item: Item) -> A {
+        let point = &self.points[item as usize];
+        let distance = query
+            .iter()
+            .zip(point.iter())
+            .map(|(a, b)| (a.to_f64() - b.to_f64()).powi(2))
+            .sum();
+
+        A::from_f64(distance)
+    }
+}
+
+/// The results of one library for every query in a [`Dataset`], each sorted by distance
+pub struct LibraryOutput<A> {
+    pub library: &'static str,
+    pub results: Vec<Vec<Neighbour<A>>>,
+}
+
+/// Runs `check` for every query in `dataset` against a tree of type `T`.
+///
+/// Returns `None` if the library does not support the check.
+pub fn run_check<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    dataset: &Dataset<A, K>,
+    check: Check,
+) -> Option<LibraryOutput<A>> {
+    if !T::supports(check.operation()) {
+        return None;
+    }
+
+    let tree = T::build_from_slice(&dataset.points);
+
+    let results = dataset
+        .queries
+        .par_iter()
+        .map(|query| {
+            let mut result = match check {
+                Check::NearestOne => vec![tree.nearest_one(query)],
+                Check::NearestN { qty } => tree.nearest_n(query, qty),
+                Check::Within { radius } => tree.within(query, A::from_f64(radius)),
+                Check::NearestNWithin { radius, max_qty } => {
+                    tree.nearest_n_within(query, A::from_f64(radius), max_qty, true)
+                }
+
+                // best_n_within only returns items, so the distances are filled in
+                // from the dataset in order to report them
+                Check::BestNWithin { radius, max_qty } => tree
+                    .best_n_within(query, A::from_f64(radius), max_qty)
+                    .into_iter()
+                    .map(|item| Neighbour {
+                        distance: dataset.squared_distance(query, item),
+                        item,
+                    })
+                    .collect(),
+            };
+            sort_neighbours(&mut result, check);
+
+            result
+        })
+        .collect();
+
+    Some(LibraryOutput {
+        library: T::NAME,
+        results,
+    })
+}
+
+/// Puts results into a canonical order so that outputs can be compared position by position.
+/// best_n_within results are ordered by item, as that is what the query selects on.
+fn sort_neighbours<A: Scalar>(neighbours: &mut [Neighbour<A>], check: Check) {
+    match check {
+        Check::BestNWithin { .. } => neighbours.sort_by_key(|n| n.item),
+        _ => neighbours.sort_by(|a, b| {
+            a.distance
+                .partial_cmp(&b.distance)
+                .unwrap()
+                .then(a.item.cmp(&b.item))
+        }),
+    }
+}
+
+/// A single disagreement between a library and the reference library
+pub struct Mismatch {
+    pub query_idx: usize,
+    pub query: Vec<f64>,
+    pub reference: &'static str,
+    pub library: &'static str,
+
+    /// `(item, distance)` returned by the reference library at this position, if any
+    pub expected: Option<(Item, f64)>,
+
+    /// `(item, distance)` returned by the library at this position, if any
+    pub actual: Option<(Item, f64)>,
+}
+
+fn describe(result: Option<(Item, f64)>) -> String {
+    match result {
+        Some((item, distance)) => format!("item {} at distance {:e}", item, distance),
+        None => "nothing".to_string(),
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "query #{} {:?}: {} returned {}, {} returned {}",
+            self.query_idx,
+            self.query,
+            self.library,
+            describe(self.actual),
+            self.reference,
+            describe(self.expected),
+        )
+    }
+}
+
+fn distances_match<A: Scalar>(a: A, b: A) -> bool {
+    let (a, b) = (a.to_f64(), b.to_f64());
+
+    (a - b).abs() <= A::EPSILON * a.abs().max(b.abs()).max(1.0)
+}
+
+/// Whether some other result in `neighbours` is the same distance from the query as the one
+/// at `idx`. Libraries are free to return either of two equidistant items.
+fn is_tied<A: Scalar>(neighbours: &[Neighbour<A>], idx: usize) -> bool {
+    neighbours
+        .iter()
+        .enumerate()
+        .any(|(other, n)| other != idx && distances_match(n.distance, neighbours[idx].distance))
+}
+
+/// Compares the output of every library against the first, returning every disagreement
+pub fn compare_outputs<A: Scalar, const K: usize>(
+    dataset: &Dataset<A, K>,
+    outputs: &[LibraryOutput<A>],
+) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+    let Some((reference, others)) = outputs.split_first() else {
+        return mismatches;
+    };
+
+    for output in others {
+        for (query_idx, (expected, actual)) in reference
+            .results
+            .iter()
+            .zip(output.results.iter())
+            .enumerate()
+        {
+            for idx in 0..expected.len().max(actual.len()) {
+                let (exp, act) = (expected.get(idx), actual.get(idx));
+                let agree = match (exp, act) {
+                    (Some(exp), Some(act)) => {
+                        distances_match(exp.distance, act.distance)
+                            && (exp.item == act.item || is_tied(expected, idx))
+                    }
+                    _ => false,
+                };
+
+                if !agree {
+                    mismatches.push(Mismatch {
+                        query_idx,
+                        query: dataset.queries[query_idx].map(A::to_f64).to_vec(),
+                        reference: reference.library,
+                        library: output.library,
+                        expected: exp.map(|n| (n.item, n.distance.to_f64())),
+                        actual: act.map(|n| (n.item, n.distance.to_f64())),
+                    });
+                }
+            }
+        }
+    }
+
+    mismatches
+}
+
+/// Runs `check` against every listed adapter over the same dataset, for each listed
+/// dimensionality, printing any disagreements. Evaluates to the number of mismatches found.
+///
+/// Adapters can be preceded by attributes, so that those behind a disabled feature are skipped.
+macro_rules! compare_libraries {
+    ($args:expr, $check:expr, $a:ty, [$($k:tt),+ $(,)?], $adapters:tt) => {
+        0 $(+ compare_libraries!(@dims $args, $check, $a, $k, $adapters))+
+    };
+    (@dims $args:expr, $check:expr, $a:ty, $k:tt, [$($(#[$meta:meta])* $adapter:ident),* $(,)?]) => {{
+        let dataset = Dataset::<$a, $k>::seeded(
+            $args.size,
+            $args.queries,
+            $args.seed,
+            $args.distribution,
+        );
+
+        #[allow(unused_mut)]
+        let mut outputs: Vec<LibraryOutput<$a>> = vec![];
+        $(
+            $(#[$meta])*
+            outputs.extend(run_check::<$a, $adapter<$a, $k>, $k>(&dataset, $check));
+        )*
+
+        let libraries: Vec<_> = outputs.iter().map(|output| output.library).collect();
+        let mismatches = compare_outputs(&dataset, &outputs);
+        report(&dims_and_type::<$a, $k>(), &libraries, &mismatches, $args.max_reported);
+
+        mismatches.len()
+    }};
+}
+
+fn report(dims_and_type: &str, libraries: &[&str], mismatches: &[Mismatch], max_reported: usize) {
+    if mismatches.is_empty() {
+        println!("{}: {} agree", dims_and_type, libraries.join(", "));
+        return;
+    }
+
+    println!(
+        "{}: {} mismatches between {}",
+        dims_and_type,
+        mismatches.len(),
+        libraries.join(", ")
+    );
+    for mismatch in mismatches.iter().take(max_reported) {
+        println!("    {}", mismatch);
+    }
+    if mismatches.len() > max_reported {
+        println!("    ... and {} more", mismatches.len() - max_reported);
+    }
+}
+
+/// Runs `check` for every library, scalar type and dimensionality, returning the
+/// total number of mismatches found
+pub fn compare_all(args: &CompareArgs, check: Check) -> usize {
+    println!(
+        "Comparing {:?} over {} {} points, seed {}",
+        check,
+        args.size,
+        args.distribution.name(),
+        args.seed
+    );
+
+    let mut mismatches = 0;
+
+    mismatches += compare_libraries!(
+        args,
+        check,
+        f64,
+        [2, 3, 4],
+        [
+            BruteForce,
+            #[cfg(feature = "kiddo_v1")]
+            KiddoV1,
+            #[cfg(feature = "kiddo_v2")]
+            KiddoV2,
+            #[cfg(feature = "kiddo_v3")]
+            KiddoV3,
+            #[cfg(feature = "kiddo_v3")]
+            KiddoV3Immutable,
+            #[cfg(feature = "kiddo_v5")]
+            KiddoV5,
+            #[cfg(feature = "kiddo_v5")]
+            KiddoV5Immutable,
+            #[cfg(feature = "kiddo_next")]
+            KiddoNext,
+            #[cfg(feature = "kiddo_next")]
+            KiddoNextImmutable,
+            #[cfg(feature = "fnntw")]
+            Fnntw,
+            #[cfg(feature = "nabo")]
+            Nabo,
+            #[cfg(feature = "rstar")]
+            Rstar,
+            #[cfg(feature = "kdtree")]
+            Kdtree,
+            #[cfg(feature = "kd-tree")]
+            KdTree,
+            #[cfg(feature = "petal-neighbors")]
+            PetalBallTree,
+        ]
+    );
+    mismatches += compare_libraries!(
+        args,
+        check,
+        f32,
+        [2, 3, 4],
+        [
+            BruteForce,
+            #[cfg(feature = "kiddo_v1")]
+            KiddoV1,
+            #[cfg(feature = "kiddo_v2")]
+            KiddoV2,
+            #[cfg(feature = "kiddo_v3")]
+            KiddoV3,
+            #[cfg(feature = "kiddo_v3")]
+            KiddoV3Immutable,
+            #[cfg(feature = "kiddo_v5")]
+            KiddoV5,
+            #[cfg(feature = "kiddo_v5")]
+            KiddoV5Immutable,
+            #[cfg(feature = "kiddo_next")]
+            KiddoNext,
+            #[cfg(feature = "kiddo_next")]
+            KiddoNextImmutable,
+            #[cfg(feature = "nabo")]
+            Nabo,
+            #[cfg(feature = "rstar")]
+            Rstar,
+            #[cfg(feature = "kdtree")]
+            Kdtree,
+            #[cfg(feature = "kd-tree")]
+            KdTree,
+            #[cfg(feature = "petal-neighbors")]
+            PetalBallTree,
+        ]
+    );
+    #[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+    {
+        mismatches += compare_libraries!(
+            args,
+            check,
+            FXP,
+            [2, 3, 4],
+            [
+                #[cfg(feature = "kiddo_v2")]
+                KiddoV2Fixed,
+                #[cfg(feature = "kiddo_v3")]
+                KiddoV3Fixed,
+                #[cfg(feature = "kiddo_v5")]
+                KiddoV5Fixed,
+            ]
+        );
+    }
+
+    mismatches
+}
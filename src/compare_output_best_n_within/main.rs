@@ -0,0 +1,18 @@
+//! Checks that every library returns the same results for best_n_within queries over the same
+//! seeded dataset, exiting with a non-zero status if any of them disagree.
+
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use kd_tree_comparison::compare_output::{compare_all, Check, CompareArgs};
+
+fn main() -> ExitCode {
+    let args = CompareArgs::parse();
+
+    if compare_all(&args, Check::best_n_within(&args)) > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
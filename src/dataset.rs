@@ -0,0 +1,403 @@
+//! Seeded datasets shared between the Rust benches and the Python polyglot benches.
+//!
+//! Tree points and query points are generated once from a fixed seed and written to
+//! `.npy` files under `target/datasets`. The Rust scenarios load them through
+//! [`dataset_points`] and [`query_points`], and the Python benches `np.load` the same
+//! files in their global init, so that every library is measured on identical inputs.
+//!
+//! Coordinates are always stored as `f64`. `f32` (or fixed-point) benches convert them
+//! on load, on both sides.
+//!
+//! The [`Distribution`] that tree points and query points are drawn from is chosen per run,
+//! with the `KD_TREE_DISTRIBUTION` and `KD_TREE_QUERY_DISTRIBUTION` environment variables or
+//! [`set_distributions`].
+//!
+//! Alternatively, tree points and query points can be read from [`PointFile`]s, set with the
+//! `KD_TREE_POINTS_FILE` and `KD_TREE_QUERIES_FILE` environment variables or
+//! [`set_point_files`]. Trees of each size are then built from a seeded sample of the points
+//! file, or from the whole file in place if it has exactly that many points. Without a
+//! queries file, query points are a separate seeded sample of the points file.
+//!
+//! Float trees can be compared against fixed point trees on equal terms by loading their
+//! datasets [`with_quantization`], which rounds every coordinate to the fixed point type first.
+//!
+//! Either way, anything other than uniform generated points is recorded in benchmark ids
+//! through [`dataset_tags`].
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::distribution::Distribution;
+use crate::point_file::{PointFile, Points, NPY_MAGIC};
+use crate::scalar::Scalar;
+
+/// Seed that every shared dataset is derived from
+pub const DATASET_SEED: u64 = 42;
+
+/// Overrides the directory that datasets are written to and read from
+pub const DATASET_DIR_ENV: &str = "KD_TREE_DATASET_DIR";
+
+/// Distribution that tree points are drawn from, by [name](Distribution::name). Defaults to uniform.
+pub const DISTRIBUTION_ENV: &str = "KD_TREE_DISTRIBUTION";
+
+/// Distribution that query points are drawn from. Defaults to the tree points' distribution.
+pub const QUERY_DISTRIBUTION_ENV: &str = "KD_TREE_QUERY_DISTRIBUTION";
+
+/// File that tree points are read from, instead of being generated
+pub const POINTS_FILE_ENV: &str = "KD_TREE_POINTS_FILE";
+
+/// File that query points are read from. Defaults to sampling the points file.
+pub const QUERIES_FILE_ENV: &str = "KD_TREE_QUERIES_FILE";
+
+/// Dimensions of the points in raw binary point files, which can not be read from the files
+pub const FILE_DIMS_ENV: &str = "KD_TREE_FILE_DIMS";
+
+static DISTRIBUTIONS: OnceLock<Distributions> = OnceLock::new();
+static POINT_FILES: OnceLock<Option<PointFiles>> = OnceLock::new();
+static QUANTIZATION: RwLock<Option<Quantization>> = RwLock::new(None);
+
+/// The distributions that a run draws its tree points and query points from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Distributions {
+    pub points: Distribution,
+    pub queries: Distribution,
+}
+
+impl Distributions {
+    fn from_env() -> Self {
+        let from_env = |var: &str| {
+            std::env::var(var).ok().map(|name| {
+                Distribution::from_name(&name)
+                    .unwrap_or_else(|| panic!("unknown distribution '{}' in {}", name, var))
+            })
+        };
+
+        let points = from_env(DISTRIBUTION_ENV).unwrap_or_default();
+        let queries = from_env(QUERY_DISTRIBUTION_ENV).unwrap_or(points);
+
+        Distributions { points, queries }
+    }
+
+    pub fn for_role(&self, role: DatasetRole) -> Distribution {
+        match role {
+            DatasetRole::Points => self.points,
+            DatasetRole::Queries => self.queries,
+        }
+    }
+}
+
+/// The distributions used by this run
+pub fn distributions() -> Distributions {
+    *DISTRIBUTIONS.get_or_init(Distributions::from_env)
+}
+
+/// Overrides the distributions given by the environment. Must be called before any
+/// dataset is loaded, and returns the distributions already in use otherwise.
+pub fn set_distributions(distributions: Distributions) -> Result<(), Distributions> {
+    DISTRIBUTIONS.set(distributions)
+}
+
+/// The files that a run reads its tree points and query points from
+#[derive(Debug)]
+pub struct PointFiles {
+    pub points: PointFile,
+
+    /// Query points are sampled from `points` if there is no queries file
+    pub queries: Option<PointFile>,
+}
+
+impl PointFiles {
+    fn from_env() -> Option<Self> {
+        let dims = std::env::var(FILE_DIMS_ENV).ok().map(|dims| {
+            dims.parse()
+                .unwrap_or_else(|_| panic!("invalid dimensions '{}' in {}", dims, FILE_DIMS_ENV))
+        });
+        let from_env = |var: &str| {
+            std::env::var_os(var).map(|path| {
+                PointFile::new(path, dims).unwrap_or_else(|err| panic!("invalid {}: {}", var, err))
+            })
+        };
+
+        Some(PointFiles {
+            points: from_env(POINTS_FILE_ENV)?,
+            queries: from_env(QUERIES_FILE_ENV),
+        })
+    }
+
+    /// The file to read a dataset from. Query points come from the points file if there is no queries file.
+    pub fn for_role(&self, role: DatasetRole) -> &PointFile {
+        match role {
+            DatasetRole::Points => &self.points,
+            DatasetRole::Queries => self.queries.as_ref().unwrap_or(&self.points),
+        }
+    }
+}
+
+/// The point files used by this run, if its points are not generated
+pub fn point_files() -> Option<&'static PointFiles> {
+    POINT_FILES.get_or_init(PointFiles::from_env).as_ref()
+}
+
+/// Overrides the point files given by the environment. Must be called before any
+/// dataset is loaded, and returns the files back otherwise.
+pub fn set_point_files(files: PointFiles) -> Result<(), Box<PointFiles>> {
+    POINT_FILES
+        .set(Some(files))
+        .map_err(|files| Box::new(files.expect("only set to some files")))
+}
+
+/// Whether trees of `qty` points can be built for `dims` dimensions. Always true for
+/// generated points, while point files only hold so many points of one dimensionality.
+pub fn dataset_available(dims: usize, qty: usize) -> bool {
+    let Some(files) = point_files() else {
+        return true;
+    };
+
+    let fits = |file: &PointFile| file.dims().is_ok_and(|file_dims| file_dims == dims);
+
+    fits(files.for_role(DatasetRole::Queries))
+        && fits(&files.points)
+        && files.points.len().is_ok_and(|len| qty <= len)
+}
+
+/// The fixed point type that datasets are currently rounded to
+#[derive(Clone, Copy)]
+struct Quantization {
+    name: &'static str,
+    quantize: fn(f64) -> f64,
+}
+
+fn quantize<F: Scalar>(value: f64) -> f64 {
+    F::from_f64(value).to_f64()
+}
+
+/// Runs `f` with every dataset that it loads rounded to the fixed point type `F`, so that
+/// float trees are built and queried with exactly the coordinates that fixed point trees
+/// over `F` see. Benchmark ids are tagged ` quantized=<F>`.
+///
+/// Only the Rust scenarios are affected, not the datasets written for the Python benches.
+pub fn with_quantization<F: Scalar, R>(f: impl FnOnce() -> R) -> R {
+    let previous = QUANTIZATION.write().unwrap().replace(Quantization {
+        name: F::NAME,
+        quantize: quantize::<F>,
+    });
+    let result = f();
+    *QUANTIZATION.write().unwrap() = previous;
+
+    result
+}
+
+fn quantization() -> Option<Quantization> {
+    *QUANTIZATION.read().unwrap()
+}
+
+/// Suffix for the subtype of benchmark ids, recording where points came from if not from
+/// the default of uniform generated points. Eg ` dist=gaussian_clusters`,
+/// ` dist=lines query_dist=uniform`, ` points=bunny queries=scan_2` or ` quantized=U8F8`.
+pub fn dataset_tags() -> String {
+    let mut tags = String::new();
+
+    if let Some(files) = point_files() {
+        tags.push_str(&format!(" points={}", files.points.name()));
+        if let Some(queries) = &files.queries {
+            tags.push_str(&format!(" queries={}", queries.name()));
+        }
+    } else {
+        let Distributions { points, queries } = distributions();
+        if points != Distribution::Uniform {
+            tags.push_str(&format!(" dist={}", points.name()));
+        }
+        if queries != points {
+            tags.push_str(&format!(" query_dist={}", queries.name()));
+        }
+    }
+
+    if let Some(quantization) = quantization() {
+        tags.push_str(&format!(" quantized={}", quantization.name));
+    }
+
+    tags
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatasetRole {
+    /// Points that trees are built from
+    Points,
+
+    /// Points that trees are queried with
+    Queries,
+}
+
+impl DatasetRole {
+    fn name(&self) -> &'static str {
+        match self {
+            DatasetRole::Points => "points",
+            DatasetRole::Queries => "queries",
+        }
+    }
+}
+
+pub fn dataset_dir() -> PathBuf {
+    std::env::var_os(DATASET_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("target/datasets"))
+}
+
+/// Absolute path of the `.npy` file holding a dataset,
+/// eg `target/datasets/points_uniform_3d_1000_seed42.npy`
+pub fn dataset_path(
+    role: DatasetRole,
+    distribution: Distribution,
+    dims: usize,
+    qty: usize,
+) -> PathBuf {
+    dataset_dir().join(format!(
+        "{}_{}_{}d_{}_seed{}.npy",
+        role.name(),
+        distribution.name(),
+        dims,
+        qty,
+        DATASET_SEED
+    ))
+}
+
+/// Each dataset gets its own seed, so that query points are independent of tree points
+/// and datasets of different sizes or distributions are independent of each other
+fn seed_for(role: DatasetRole, distribution: Distribution, dims: usize, qty: usize) -> u64 {
+    let role = match role {
+        DatasetRole::Points => 0u64,
+        DatasetRole::Queries => 1u64,
+    };
+
+    DATASET_SEED ^ (role << 63) ^ ((distribution as u64) << 56) ^ ((dims as u64) << 48) ^ qty as u64
+}
+
+/// Generates the dataset for this run's distributions if it has not been written yet,
+/// returning the path of its `.npy` file.
+///
+/// When reading from point files, the sample of the file that [`load_dataset`] would
+/// use is written out instead, so that the Python benches can `np.load` it.
+pub fn ensure_dataset<const K: usize>(role: DatasetRole, qty: usize) -> PathBuf {
+    if let Some(files) = point_files() {
+        let file = files.for_role(role);
+        let path = dataset_dir().join(format!(
+            "{}_file-{}_{}d_{}_seed{}.npy",
+            role.name(),
+            file.name(),
+            K,
+            qty,
+            DATASET_SEED
+        ));
+        if !path.is_file() {
+            let points: Vec<[f64; K]> = load_file::<f64, K>(file, role, qty).to_vec();
+            write_dataset(&path, &points);
+        }
+
+        return path;
+    }
+
+    let distribution = distributions().for_role(role);
+    let path = dataset_path(role, distribution, K, qty);
+    if path.is_file() {
+        return path;
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed_for(role, distribution, K, qty));
+    let points = distribution.sample::<_, K>(qty, &mut rng);
+    write_dataset(&path, &points);
+
+    path
+}
+
+fn write_dataset<const K: usize>(path: &Path, points: &[[f64; K]]) {
+    fs::create_dir_all(dataset_dir()).expect("could not create dataset directory");
+
+    // Written to a temporary file first so that a concurrently running bench
+    // never reads a partially written dataset
+    let tmp_path = path.with_extension(format!("npy.{}.tmp", std::process::id()));
+    write_npy(&tmp_path, points).expect("could not write dataset");
+    fs::rename(&tmp_path, path).expect("could not write dataset");
+}
+
+fn load_file<A: Scalar, const K: usize>(
+    file: &PointFile,
+    role: DatasetRole,
+    qty: usize,
+) -> Points<A, K> {
+    file.load(qty, seed_for(role, Distribution::Uniform, K, qty))
+        .unwrap_or_else(|err| {
+            panic!(
+                "could not read points from {}: {}",
+                file.path().display(),
+                err
+            )
+        })
+}
+
+/// Loads a shared dataset, generating it first if needed
+pub fn load_dataset<A: Scalar, const K: usize>(role: DatasetRole, qty: usize) -> Points<A, K> {
+    let Some(quantization) = quantization() else {
+        return load_exact(role, qty);
+    };
+
+    load_exact::<f64, K>(role, qty)
+        .iter()
+        .map(|point| point.map(|coord| A::from_f64((quantization.quantize)(coord))))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn load_exact<A: Scalar, const K: usize>(role: DatasetRole, qty: usize) -> Points<A, K> {
+    if let Some(files) = point_files() {
+        return load_file(files.for_role(role), role, qty);
+    }
+
+    let path = ensure_dataset::<K>(role, qty);
+    let file = PointFile::new(&path, Some(K))
+        .unwrap_or_else(|err| panic!("could not read dataset {}: {}", path.display(), err));
+
+    load_file(&file, role, qty)
+}
+
+/// The shared set of `qty` points that trees are built from
+pub fn dataset_points<A: Scalar, const K: usize>(qty: usize) -> Points<A, K> {
+    load_dataset(DatasetRole::Points, qty)
+}
+
+/// The shared set of `qty` points that trees are queried with
+pub fn query_points<A: Scalar, const K: usize>(qty: usize) -> Points<A, K> {
+    load_dataset(DatasetRole::Queries, qty)
+}
+
+/// Writes points as a C-ordered `(points.len(), K)` array of little-endian `f64`s, in NPY v1.0 format
+pub fn write_npy<const K: usize>(path: &Path, points: &[[f64; K]]) -> io::Result<()> {
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+        points.len(),
+        K
+    );
+
+    // The header is padded with spaces and terminated with a newline so that the
+    // data that follows it starts on a 64 byte boundary
+    let unpadded_len = NPY_MAGIC.len() + 2 + 2 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded_len % 64) % 64));
+    header.push('\n');
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(NPY_MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for point in points {
+        for coord in point {
+            writer.write_all(&coord.to_le_bytes())?;
+        }
+    }
+
+    writer.flush()
+}
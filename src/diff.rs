@@ -0,0 +1,276 @@
+//! Comparison of the same benchmarks across two runs, eg before and after a layout change.
+//!
+//! Benchmarks are matched by id. The speedup of each is the ratio of the mean time per
+//! iteration in the baseline to that in the candidate, so values above 1 are improvements.
+//! Its confidence interval is bootstrapped from the raw samples of both runs.
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+use crate::ndjson::{BenchmarkComplete, NOISE_THRESHOLD};
+
+/// Confidence level of the speedup intervals
+pub const CONFIDENCE_LEVEL: f64 = 0.95;
+
+/// Seed for the bootstrap resampling, so that repeated diffs of the same files agree
+const BOOTSTRAP_SEED: u64 = 0x6b64_7472_6565;
+
+/// The change in one benchmark between two runs
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub id: String,
+
+    /// Mean time per iteration in the baseline run, in ns
+    pub baseline: f64,
+
+    /// Mean time per iteration in the candidate run, in ns
+    pub candidate: f64,
+
+    /// `baseline / candidate`. Above 1 means the candidate is faster.
+    pub speedup: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+
+    /// Whether the whole confidence interval lies outside the noise threshold
+    pub significant: bool,
+}
+
+/// Result of diffing one run against another
+pub struct RunDiff {
+    /// Matched benchmarks, sorted from the biggest win to the biggest loss
+    pub comparisons: Vec<Comparison>,
+    pub only_in_baseline: Vec<String>,
+    pub only_in_candidate: Vec<String>,
+}
+
+/// Time per iteration of each of a benchmark's samples, in ns. Samples of no iterations
+/// are left out.
+fn sample_times(benchmark: &BenchmarkComplete) -> Vec<f64> {
+    benchmark
+        .measured_values
+        .iter()
+        .zip(benchmark.iteration_count.iter())
+        .map(|(&time, &iters)| time / iters as f64)
+        .filter(|time| time.is_finite())
+        .collect()
+}
+
+/// Whether a benchmark has any samples to compare. Records without them, eg from runs that
+/// were interrupted, would otherwise give a mean time of NaN.
+fn has_samples(benchmark: &BenchmarkComplete) -> bool {
+    !sample_times(benchmark).is_empty()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn resampled_mean<R: Rng>(values: &[f64], rng: &mut R) -> f64 {
+    let sum: f64 = (0..values.len())
+        .map(|_| values[rng.gen_range(0..values.len())])
+        .sum();
+
+    sum / values.len() as f64
+}
+
+/// Compares one benchmark across two runs, with `resamples` bootstrap resamples
+/// for the speedup's confidence interval
+pub fn compare(
+    baseline: &BenchmarkComplete,
+    candidate: &BenchmarkComplete,
+    resamples: usize,
+) -> Comparison {
+    let baseline_times = sample_times(baseline);
+    let candidate_times = sample_times(candidate);
+    let (baseline_mean, candidate_mean) = (mean(&baseline_times), mean(&candidate_times));
+
+    let mut rng = StdRng::seed_from_u64(BOOTSTRAP_SEED);
+    let mut speedups: Vec<f64> = (0..resamples)
+        .map(|_| {
+            resampled_mean(&baseline_times, &mut rng) / resampled_mean(&candidate_times, &mut rng)
+        })
+        .collect();
+    speedups.sort_by(f64::total_cmp);
+
+    let tail = (1.0 - CONFIDENCE_LEVEL) / 2.0;
+    let percentile = |p: f64| speedups[((resamples - 1) as f64 * p).round() as usize];
+    let (lower_bound, upper_bound) = (percentile(tail), percentile(1.0 - tail));
+
+    Comparison {
+        id: baseline.id.clone(),
+        baseline: baseline_mean,
+        candidate: candidate_mean,
+        speedup: baseline_mean / candidate_mean,
+        lower_bound,
+        upper_bound,
+        significant: lower_bound > 1.0 + NOISE_THRESHOLD || upper_bound < 1.0 - NOISE_THRESHOLD,
+    }
+}
+
+/// Matches the benchmarks of two runs by id and compares each pair. Benchmarks without
+/// any samples are skipped.
+pub fn diff_runs(
+    baseline: &[BenchmarkComplete],
+    candidate: &[BenchmarkComplete],
+    resamples: usize,
+) -> RunDiff {
+    let baseline: Vec<&BenchmarkComplete> = baseline.iter().filter(|b| has_samples(b)).collect();
+    let candidate: Vec<&BenchmarkComplete> = candidate.iter().filter(|b| has_samples(b)).collect();
+
+    let candidates: HashMap<&str, &BenchmarkComplete> = candidate
+        .iter()
+        .map(|&benchmark| (benchmark.id.as_str(), benchmark))
+        .collect();
+    let baselines: HashMap<&str, &BenchmarkComplete> = baseline
+        .iter()
+        .map(|&benchmark| (benchmark.id.as_str(), benchmark))
+        .collect();
+
+    let mut comparisons: Vec<Comparison> = baseline
+        .par_iter()
+        .filter_map(|benchmark| {
+            candidates
+                .get(benchmark.id.as_str())
+                .map(|candidate| compare(benchmark, candidate, resamples))
+        })
+        .collect();
+    comparisons.sort_by(|a, b| b.speedup.total_cmp(&a.speedup));
+
+    RunDiff {
+        comparisons,
+        only_in_baseline: baseline
+            .iter()
+            .filter(|benchmark| !candidates.contains_key(benchmark.id.as_str()))
+            .map(|benchmark| benchmark.id.clone())
+            .collect(),
+        only_in_candidate: candidate
+            .iter()
+            .filter(|benchmark| !baselines.contains_key(benchmark.id.as_str()))
+            .map(|benchmark| benchmark.id.clone())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ndjson::Estimate;
+
+    /// A benchmark of 10 iterations per sample, taking `times` ns per iteration
+    fn benchmark(id: &str, times: &[f64]) -> BenchmarkComplete {
+        let estimate = Estimate {
+            estimate: 0.0,
+            lower_bound: 0.0,
+            upper_bound: 0.0,
+            unit: "ns".to_string(),
+        };
+
+        BenchmarkComplete {
+            id: id.to_string(),
+            report_directory: String::new(),
+            iteration_count: vec![10; times.len()],
+            measured_values: times.iter().map(|time| time * 10.0).collect(),
+            unit: "ns".to_string(),
+            throughput: vec![],
+            typical: estimate.clone(),
+            mean: estimate.clone(),
+            median: estimate.clone(),
+            median_abs_dev: estimate,
+            slope: None,
+            change: None,
+        }
+    }
+
+    /// Times spread evenly around `centre`, by up to 5% either side
+    fn times_around(centre: f64) -> Vec<f64> {
+        (0..20)
+            .map(|idx| centre * (0.95 + 0.1 * idx as f64 / 19.0))
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_benchmark_is_not_significant() {
+        let baseline = benchmark("a", &[100.0; 20]);
+        let candidate = benchmark("a", &[100.0; 20]);
+
+        let comparison = compare(&baseline, &candidate, 1_000);
+
+        assert_eq!(comparison.speedup, 1.0);
+        assert_eq!(comparison.lower_bound, 1.0);
+        assert_eq!(comparison.upper_bound, 1.0);
+        assert!(!comparison.significant);
+    }
+
+    #[test]
+    fn interval_brackets_a_significant_speedup() {
+        let baseline = benchmark("a", &times_around(200.0));
+        let candidate = benchmark("a", &times_around(100.0));
+
+        let comparison = compare(&baseline, &candidate, 1_000);
+
+        assert!((comparison.speedup - 2.0).abs() < 1e-9);
+        assert!(comparison.lower_bound <= comparison.speedup);
+        assert!(comparison.upper_bound >= comparison.speedup);
+        assert!(comparison.lower_bound > 1.9 && comparison.upper_bound < 2.1);
+        assert!(comparison.significant);
+    }
+
+    #[test]
+    fn overlapping_samples_are_within_noise() {
+        let baseline = benchmark("a", &times_around(100.0));
+        let mut candidate_times = times_around(100.0);
+        candidate_times.reverse();
+        let candidate = benchmark("a", &candidate_times);
+
+        let comparison = compare(&baseline, &candidate, 1_000);
+
+        assert!(comparison.lower_bound < 1.0 && comparison.upper_bound > 1.0);
+        assert!(!comparison.significant);
+    }
+
+    #[test]
+    fn bootstrap_is_repeatable() {
+        let baseline = benchmark("a", &times_around(150.0));
+        let candidate = benchmark("a", &times_around(120.0));
+
+        assert_eq!(
+            compare(&baseline, &candidate, 500),
+            compare(&baseline, &candidate, 500)
+        );
+    }
+
+    #[test]
+    fn diff_matches_by_id_and_skips_benchmarks_without_samples() {
+        let baseline = vec![
+            benchmark("slower", &times_around(100.0)),
+            benchmark("faster", &times_around(200.0)),
+            benchmark("empty", &[]),
+            benchmark("removed", &times_around(100.0)),
+        ];
+        let candidate = vec![
+            benchmark("faster", &times_around(100.0)),
+            benchmark("slower", &times_around(200.0)),
+            benchmark("empty", &[]),
+            benchmark("added", &times_around(100.0)),
+        ];
+
+        let diff = diff_runs(&baseline, &candidate, 200);
+
+        let ids: Vec<&str> = diff.comparisons.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["faster", "slower"]);
+        assert_eq!(diff.only_in_baseline, ["removed"]);
+        assert_eq!(diff.only_in_candidate, ["added"]);
+    }
+
+    #[test]
+    fn samples_of_no_iterations_are_ignored() {
+        let mut baseline = benchmark("a", &[100.0; 5]);
+        baseline.iteration_count.push(0);
+        baseline.measured_values.push(0.0);
+
+        assert_eq!(sample_times(&baseline), [100.0; 5]);
+    }
+}
@@ -0,0 +1,219 @@
+//! Distributions that tree points and query points can be drawn from.
+//!
+//! Every distribution produces points within the unit hypercube, [0, 1) in every dimension,
+//! so that the radii used by the scenarios stay meaningful. Structured distributions
+//! (clusters, lines, planes, spheres) generate their structure from a fixed seed that only
+//! depends on the dimensionality, so that tree points and query points drawn from the same
+//! distribution share the same clusters, lines, etc.
+
+use std::f64::consts::PI;
+
+use clap::ValueEnum;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Seed for the clusters, lines, planes and spheres of the structured distributions
+const STRUCTURE_SEED: u64 = 0x5e_ed0f_5747;
+
+const CLUSTER_QTY: usize = 16;
+const CLUSTER_STD_DEV: f64 = 0.02;
+
+/// Exponent applied to uniform coordinates for [`Distribution::PowerLaw`]. Density
+/// falls away from the origin as `x^(1 / POWER_LAW_EXPONENT - 1)`.
+const POWER_LAW_EXPONENT: f64 = 3.0;
+
+const LINE_QTY: usize = 8;
+const PLANE_QTY: usize = 4;
+const SPHERE_QTY: usize = 8;
+
+/// Average number of points sharing each grid position in [`Distribution::GridDuplicates`]
+const GRID_DUPLICATES_PER_POSITION: usize = 4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Distribution {
+    /// Uniform over the unit hypercube
+    #[default]
+    Uniform,
+
+    /// Gaussian blobs around a fixed set of random centres
+    GaussianClusters,
+
+    /// Density following a power law, concentrated towards the origin
+    PowerLaw,
+
+    /// Points on a fixed set of line segments
+    Lines,
+
+    /// Points on a fixed set of triangular patches of planes
+    Planes,
+
+    /// Points on the surfaces of a fixed set of hyperspheres
+    Spheres,
+
+    /// Points snapped to a regular grid, with several exact duplicates at each grid position
+    GridDuplicates,
+}
+
+impl Distribution {
+    /// Name of the distribution as it appears in benchmark ids and dataset file names
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::Uniform => "uniform",
+            Distribution::GaussianClusters => "gaussian_clusters",
+            Distribution::PowerLaw => "power_law",
+            Distribution::Lines => "lines",
+            Distribution::Planes => "planes",
+            Distribution::Spheres => "spheres",
+            Distribution::GridDuplicates => "grid_duplicates",
+        }
+    }
+
+    /// Parses a distribution from its [name](Distribution::name)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Distribution::value_variants()
+            .iter()
+            .copied()
+            .find(|distribution| distribution.name() == name)
+    }
+
+    /// Draws `qty` points from this distribution
+    pub fn sample<R: Rng, const K: usize>(&self, qty: usize, rng: &mut R) -> Vec<[f64; K]> {
+        let mut structure_rng = StdRng::seed_from_u64(STRUCTURE_SEED ^ K as u64);
+
+        match self {
+            Distribution::Uniform => (0..qty).map(|_| uniform_point(rng)).collect(),
+
+            Distribution::GaussianClusters => {
+                let centres: Vec<[f64; K]> = (0..CLUSTER_QTY)
+                    .map(|_| uniform_point(&mut structure_rng))
+                    .collect();
+
+                (0..qty)
+                    .map(|_| {
+                        let centre = &centres[rng.gen_range(0..CLUSTER_QTY)];
+                        within_unit_cube(rng, |rng| {
+                            std::array::from_fn(|dim| {
+                                centre[dim] + standard_normal(rng) * CLUSTER_STD_DEV
+                            })
+                        })
+                    })
+                    .collect()
+            }
+
+            Distribution::PowerLaw => (0..qty)
+                .map(|_| uniform_point::<_, K>(rng).map(|coord| coord.powf(POWER_LAW_EXPONENT)))
+                .collect(),
+
+            Distribution::Lines => {
+                // The segments join two points of the cube, so every point on them is inside it
+                let lines: Vec<([f64; K], [f64; K])> = (0..LINE_QTY)
+                    .map(|_| {
+                        (
+                            uniform_point(&mut structure_rng),
+                            uniform_point(&mut structure_rng),
+                        )
+                    })
+                    .collect();
+
+                (0..qty)
+                    .map(|_| {
+                        let (start, end) = &lines[rng.gen_range(0..LINE_QTY)];
+                        let t: f64 = rng.gen();
+                        std::array::from_fn(|dim| start[dim] + t * (end[dim] - start[dim]))
+                    })
+                    .collect()
+            }
+
+            Distribution::Planes => {
+                // Triangles between three points of the cube, which are inside it for the same reason
+                let planes: Vec<[[f64; K]; 3]> = (0..PLANE_QTY)
+                    .map(|_| std::array::from_fn(|_| uniform_point(&mut structure_rng)))
+                    .collect();
+
+                (0..qty)
+                    .map(|_| {
+                        let [a, b, c] = &planes[rng.gen_range(0..PLANE_QTY)];
+                        let (mut s, mut t): (f64, f64) = (rng.gen(), rng.gen());
+                        if s + t > 1.0 {
+                            (s, t) = (1.0 - s, 1.0 - t);
+                        }
+                        std::array::from_fn(|dim| {
+                            a[dim] + s * (b[dim] - a[dim]) + t * (c[dim] - a[dim])
+                        })
+                    })
+                    .collect()
+            }
+
+            Distribution::Spheres => {
+                let spheres: Vec<([f64; K], f64)> = (0..SPHERE_QTY)
+                    .map(|_| {
+                        let centre =
+                            uniform_point::<_, K>(&mut structure_rng).map(|c| 0.2 + c * 0.6);
+                        let radius = structure_rng.gen_range(0.05..0.2);
+                        (centre, radius)
+                    })
+                    .collect();
+
+                (0..qty)
+                    .map(|_| {
+                        let (centre, radius) = &spheres[rng.gen_range(0..SPHERE_QTY)];
+                        within_unit_cube(rng, |rng| {
+                            let direction = unit_vector::<_, K>(rng);
+                            std::array::from_fn(|dim| centre[dim] + radius * direction[dim])
+                        })
+                    })
+                    .collect()
+            }
+
+            Distribution::GridDuplicates => {
+                let positions = (qty / GRID_DUPLICATES_PER_POSITION).max(1);
+                let cells_per_dim =
+                    ((positions as f64).powf(1.0 / K as f64).round() as usize).max(2);
+
+                (0..qty)
+                    .map(|_| {
+                        std::array::from_fn(|_| {
+                            let cell = rng.gen_range(0..cells_per_dim);
+                            (cell as f64 + 0.5) / cells_per_dim as f64
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn uniform_point<R: Rng, const K: usize>(rng: &mut R) -> [f64; K] {
+    std::array::from_fn(|_| rng.gen())
+}
+
+/// Samples from the standard normal distribution, using the Box-Muller transform
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+fn unit_vector<R: Rng, const K: usize>(rng: &mut R) -> [f64; K] {
+    loop {
+        let vector: [f64; K] = std::array::from_fn(|_| standard_normal(rng));
+        let norm = vector.iter().map(|c| c * c).sum::<f64>().sqrt();
+        if norm > f64::EPSILON {
+            return vector.map(|c| c / norm);
+        }
+    }
+}
+
+/// Redraws points from `sample` until one falls within the unit hypercube
+fn within_unit_cube<R: Rng, const K: usize>(
+    rng: &mut R,
+    mut sample: impl FnMut(&mut R) -> [f64; K],
+) -> [f64; K] {
+    loop {
+        let point = sample(rng);
+        if point.iter().all(|coord| (0.0..1.0).contains(coord)) {
+            return point;
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Builds and runs the suite once per variant, each with its own set of cargo features, and
+//! merges the results into one NDJSON file with each benchmark id tagged by its variant.
+//! With `--codegen`, each variant is also rebuilt under each of the given codegen profiles,
+//! and ids are tagged with the profile too.
+//!
+//! ```bash
+//! cargo run --release --bin kdab -- \
+//!     --variant std=kiddo_v5,kiddo_next,kiddo_next/simd \
+//!     --variant mveb=kiddo_v5,kiddo_next,kiddo_next/simd,kiddo_next/modified_van_emde_boas \
+//!     --output mveb-vs-std.ndjson -- --bench nearest_one
+//! cargo run --release --bin ndjson-diff -- mveb-vs-std.ndjson --baseline-variant std
+//!
+//! cargo run --release --bin kdab -- --codegen x86-64,x86-64-v2,x86-64-v3,native --kdbench \
+//!     --output cpu-levels.ndjson -- --library kiddo-v5-immutable --query nearest-one
+//! cargo run --release --bin ndjson-diff -- cpu-levels.ndjson --baseline-codegen x86-64
+//! ```
+//!
+//! Each variant is built with `--no-default-features`, and each run into its own target
+//! directory under `target/ab`, so that switching between them does not rebuild everything
+//! and criterion's saved baselines of one run are never compared against another. Arguments
+//! after `--` are passed on to `cargo criterion`, or to `kdbench` with `--kdbench`.
+//!
+//! The `pgo` profile builds an instrumented suite first and runs the same benchmarks with it
+//! to train on, which needs `llvm-profdata` (from the `llvm-tools-preview` rustup component,
+//! or set `LLVM_PROFDATA`) to merge the profiles.
+
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use clap::Parser;
+
+use kd_tree_comparison::codegen::{pgo_generate_flag, pgo_use_flags, CodegenProfile, CODEGEN_TAG};
+use kd_tree_comparison::ndjson::{read_messages, write_message, Message};
+use kd_tree_comparison::variants::{tag_message, Variant, VARIANT_TAG};
+
+#[derive(Parser, Debug)]
+#[command(about = "Run the benchmarks once per set of cargo features and merge the results")]
+struct Args {
+    /// A variant to build, as `<name>=<feature>,<feature>,...`. Features of the compared
+    /// libraries, such as `kiddo_next/modified_van_emde_boas`, can be listed alongside
+    /// the suite's own. The default features are used if no variant is given.
+    #[arg(long = "variant", num_args = 1, required_unless_present = "codegen")]
+    variants: Vec<Variant>,
+
+    /// Codegen profiles to build each variant under
+    #[arg(long, value_enum, value_delimiter = ',')]
+    codegen: Vec<CodegenProfile>,
+
+    /// Run `kdbench` rather than `cargo criterion`
+    #[arg(long)]
+    kdbench: bool,
+
+    /// Directory that each run's build and raw results go in
+    #[arg(long, default_value = "target/ab")]
+    work_dir: PathBuf,
+
+    /// File to write the merged NDJSON results to
+    #[arg(long, short, default_value = "ab-benchmarks.ndjson")]
+    output: PathBuf,
+
+    /// Arguments passed on to `cargo criterion`, or to `kdbench`
+    #[arg(last = true)]
+    passthrough: Vec<String>,
+}
+
+/// One build and run of the suite: a variant under a codegen profile
+struct Run<'a> {
+    variant: Option<&'a Variant>,
+    codegen: Option<CodegenProfile>,
+}
+
+impl Run<'_> {
+    fn name(&self) -> String {
+        let parts: Vec<&str> = self
+            .variant
+            .map(|variant| variant.name.as_str())
+            .into_iter()
+            .chain(self.codegen.map(|codegen| codegen.name()))
+            .collect();
+
+        if parts.is_empty() {
+            "default".to_string()
+        } else {
+            parts.join("-")
+        }
+    }
+
+    fn tags(&self) -> Vec<(&str, &str)> {
+        self.variant
+            .map(|variant| (VARIANT_TAG, variant.name.as_str()))
+            .into_iter()
+            .chain(self.codegen.map(|codegen| (CODEGEN_TAG, codegen.name())))
+            .collect()
+    }
+}
+
+/// Builds and runs the suite with the given `RUSTFLAGS`, writing its NDJSON results to `results_path`
+fn run_suite(
+    args: &Args,
+    run: &Run,
+    target_dir: &Path,
+    rustflags: &[String],
+    results_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    if args.kdbench {
+        command.args(["run", "--release", "--bin", "kdbench"]);
+    } else {
+        command.args(["criterion", "--message-format", "json"]);
+    }
+    if let Some(variant) = run.variant {
+        command
+            .arg("--no-default-features")
+            .arg(format!("--features={}", variant.features.join(",")));
+    }
+    command.arg("--target-dir").arg(target_dir);
+    if args.kdbench {
+        command
+            .arg("--")
+            .arg("--criterion-dir")
+            .arg(target_dir.join("criterion"))
+            .arg("--output")
+            .arg(results_path)
+            .args(&args.passthrough);
+    } else {
+        command
+            .args(&args.passthrough)
+            .stdout(Stdio::from(File::create(results_path)?));
+    }
+
+    command.env("RUSTFLAGS", rustflags.join(" "));
+    if let Some(codegen) = run.codegen {
+        command.envs(codegen.cargo_env());
+    }
+
+    log::info!("Running {}: {:?}", run.name(), command);
+    let status = command.status()?;
+    if !status.success() {
+        return Err(format!("{} failed: {}", run.name(), status).into());
+    }
+
+    Ok(())
+}
+
+/// Builds an instrumented suite and runs it to train on, returning the flags
+/// that build the suite optimised with the resulting profile
+fn train_pgo(
+    args: &Args,
+    run: &Run,
+    run_dir: &Path,
+    rustflags: &[String],
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let profile_dir = run_dir.join("pgo-data");
+    if profile_dir.exists() {
+        fs::remove_dir_all(&profile_dir)?;
+    }
+    fs::create_dir_all(&profile_dir)?;
+
+    let mut training_flags = rustflags.to_vec();
+    training_flags.push(pgo_generate_flag(&profile_dir));
+    run_suite(
+        args,
+        run,
+        &run_dir.join("training"),
+        &training_flags,
+        &run_dir.join("training.ndjson"),
+    )?;
+
+    let profdata = run_dir.join("merged.profdata");
+    let llvm_profdata = std::env::var("LLVM_PROFDATA").unwrap_or_else(|_| "llvm-profdata".into());
+    let status = Command::new(&llvm_profdata)
+        .arg("merge")
+        .arg("-o")
+        .arg(&profdata)
+        .arg(&profile_dir)
+        .status()
+        .map_err(|err| format!("could not run {}: {}", llvm_profdata, err))?;
+    if !status.success() {
+        return Err(format!("{} merge failed: {}", llvm_profdata, status).into());
+    }
+
+    Ok(pgo_use_flags(&profdata))
+}
+
+/// Builds and runs one variant under one codegen profile, returning the path of its NDJSON results
+fn build_and_run(args: &Args, work_dir: &Path, run: &Run) -> Result<PathBuf, Box<dyn Error>> {
+    let run_dir = work_dir.join(run.name());
+    let results_path = work_dir.join(format!("{}.ndjson", run.name()));
+
+    // Flags from the environment are kept, as setting RUSTFLAGS would otherwise drop them
+    let mut rustflags: Vec<String> = std::env::var("RUSTFLAGS")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    if let Some(codegen) = run.codegen {
+        rustflags.extend(codegen.rustflags());
+        if codegen.needs_training() {
+            let pgo_flags = train_pgo(args, run, &run_dir, &rustflags)?;
+            rustflags.extend(pgo_flags);
+        }
+    }
+
+    run_suite(args, run, &run_dir, &rustflags, &results_path)?;
+
+    Ok(results_path)
+}
+
+fn merge(runs: &[(Run, PathBuf)], output: &Path) -> Result<usize, Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(output)?);
+    let mut benchmark_qty = 0;
+
+    for (run, path) in runs {
+        let tags = run.tags();
+        for message in read_messages(path)? {
+            if matches!(message, Message::BenchmarkComplete(_)) {
+                benchmark_qty += 1;
+            }
+            write_message(&mut writer, &tag_message(message, &tags))?;
+        }
+    }
+    writer.flush()?;
+
+    Ok(benchmark_qty)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    pretty_env_logger::init();
+    let args = Args::parse();
+
+    for (idx, variant) in args.variants.iter().enumerate() {
+        if args.variants[..idx]
+            .iter()
+            .any(|other| other.name == variant.name)
+        {
+            return Err(format!("variant '{}' is listed more than once", variant.name).into());
+        }
+    }
+
+    fs::create_dir_all(&args.work_dir)?;
+    // Absolute, as PGO profiles are written by benches that run from another directory
+    let work_dir = fs::canonicalize(&args.work_dir)?;
+
+    let variants: Vec<Option<&Variant>> = if args.variants.is_empty() {
+        vec![None]
+    } else {
+        args.variants.iter().map(Some).collect()
+    };
+    let codegens: Vec<Option<CodegenProfile>> = if args.codegen.is_empty() {
+        vec![None]
+    } else {
+        args.codegen.iter().copied().map(Some).collect()
+    };
+
+    let mut results = vec![];
+    for &variant in &variants {
+        for &codegen in &codegens {
+            let run = Run { variant, codegen };
+            let results_path = build_and_run(&args, &work_dir, &run)?;
+            results.push((run, results_path));
+        }
+    }
+
+    let benchmark_qty = merge(&results, &args.output)?;
+    println!(
+        "Wrote {} benchmarks from {} runs to {}",
+        benchmark_qty,
+        results.len(),
+        args.output.display()
+    );
+    if let Some(variant) = args.variants.first() {
+        println!(
+            "Compare variants with: cargo run --release --bin ndjson-diff -- {} --baseline-variant {}",
+            args.output.display(),
+            variant.name
+        );
+    }
+    if let Some(codegen) = args.codegen.first() {
+        println!(
+            "Compare codegen profiles with: cargo run --release --bin ndjson-diff -- {} --baseline-codegen {}",
+            args.output.display(),
+            codegen.name()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,527 @@
+//! Runs a slice of the benchmark matrix, selected on the command line, and writes the
+//! results as cargo-criterion compatible NDJSON.
+//!
+//! ```bash
+//! cargo run --release --bin kdbench -- \
+//!     --library kiddo-v5-immutable,fnntw --query nearest-n --dims 3 --scalar f64 \
+//!     --min-size 1000000 --k 10 --output kiddo-v5-vs-fnntw.ndjson
+//! ```
+//!
+//! With `--memory`, the memory footprint of each selected tree is measured too, and
+//! written to the same file as `memory-complete` records. This needs the `memory` feature,
+//! which installs a counting global allocator. Leave it off for timing runs.
+//!
+//! With `--recall`, the recall@k of each selected library is measured against
+//! `--recall-reference`, along with its build time and query throughput, and written as
+//! `recall-complete` records. Approximate libraries are run once per `--ef` value, giving
+//! a recall-vs-QPS curve for each of them.
+//!
+//! With `--sweep`, every value of `--k` and `--radius` is run into one group per query,
+//! eg `Query Nearest n`, with the value tagged in each benchmark id, eg ` k=50`.
+//!
+//! With `--leaf-size`, the libraries that take their leaf size at runtime are run once per
+//! value, with it tagged in each benchmark id, eg ` leaf=64`. Without it, the leaf size is only
+//! tagged when it is not the default.
+//!
+//! With `--points-file`, trees are built from a point cloud on disk rather than generated
+//! points. Sizes larger than the file, and dimensions other than the file's, are skipped.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use clap::Parser;
+use criterion::{AxisScale, Criterion, PlotConfiguration, Throughput};
+
+use kd_tree_comparison::adapters::{
+    ef_search, leaf_size, set_ef_search, set_leaf_size, set_tag_leaf_sizes, DEFAULT_EF_SEARCH,
+};
+use kd_tree_comparison::dataset::{
+    dataset_available, set_distributions, set_point_files, Distributions, PointFiles,
+};
+use kd_tree_comparison::distribution::Distribution;
+use kd_tree_comparison::matrix::{
+    run_matrix_point, visit_matrix_point, Library, Query, QueryParams, ScalarType,
+};
+#[cfg(feature = "memory")]
+use kd_tree_comparison::memory::CountingAllocator;
+use kd_tree_comparison::memory::MemoryRun;
+use kd_tree_comparison::ndjson::{
+    collect_benchmark_records, write_message, GroupComplete, Message,
+};
+use kd_tree_comparison::point_file::PointFile;
+use kd_tree_comparison::recall::{ExactNeighbours, RecallRun};
+use kd_tree_comparison::scenarios::{size_within_dims_cap, K_SWEEP, RADIUS_SWEEP, SIZES};
+use kd_tree_comparison::workload::{
+    OperationMix, Removal, RemovalOrder, REMOVAL_ORDERS, REMOVAL_PERCENTAGES, WORKLOAD_MIXES,
+};
+
+// Only installed with the memory feature, so that timing runs allocate exactly as the
+// criterion benches do
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Parser, Debug)]
+#[command(about = "Run a slice of the k-d tree benchmark matrix")]
+struct Args {
+    /// Libraries to benchmark
+    #[arg(long, value_enum, value_delimiter = ',', required = true)]
+    library: Vec<Library>,
+
+    /// Queries to benchmark
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        required_unless_present_any = ["memory", "recall"]
+    )]
+    query: Vec<Query>,
+
+    /// Measure the bytes retained by each tree after construction, and the peak bytes
+    /// allocated during construction
+    #[arg(long)]
+    memory: bool,
+
+    /// Measure the recall@k of each library against the reference library, with its build
+    /// time and query throughput
+    #[arg(long)]
+    recall: bool,
+
+    /// Exact library whose results recall is measured against
+    #[arg(long, value_enum, default_value_t = Library::KiddoV5Immutable)]
+    recall_reference: Library,
+
+    /// Search candidate list sizes for the approximate libraries. Each value is run separately.
+    #[arg(long, value_delimiter = ',', default_values_t = [DEFAULT_EF_SEARCH])]
+    ef: Vec<usize>,
+
+    /// Leaf sizes for the libraries that take theirs at runtime. Each value is run separately.
+    /// Defaults to 32, or KD_TREE_LEAF_SIZE if set. kiddo v2 onwards are always run with
+    /// their compiled bucket size; the leaf_sizes bench sweeps theirs.
+    #[arg(long, value_delimiter = ',')]
+    leaf_size: Vec<usize>,
+
+    /// Dimensions to benchmark. Any of 2, 3, 4, 6, 8, 12, 16, 32 and 64.
+    #[arg(long, value_delimiter = ',', default_values_t = [2, 3, 4])]
+    dims: Vec<usize>,
+
+    /// Scalar types to benchmark
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [ScalarType::F32, ScalarType::F64])]
+    scalar: Vec<ScalarType>,
+
+    /// Distribution that tree points are drawn from
+    #[arg(long, value_enum, default_value_t = Distribution::Uniform)]
+    distribution: Distribution,
+
+    /// Distribution that query points are drawn from. Defaults to the tree points' distribution.
+    #[arg(long, value_enum)]
+    query_distribution: Option<Distribution>,
+
+    /// File to read tree points from, instead of generating them. One of .npy, .csv, .f32 or .f64.
+    #[arg(long)]
+    points_file: Option<PathBuf>,
+
+    /// File to read query points from. Defaults to sampling the points file.
+    #[arg(long, requires = "points_file")]
+    queries_file: Option<PathBuf>,
+
+    /// Dimensions of the points in raw .f32 or .f64 point files
+    #[arg(long)]
+    file_dims: Option<usize>,
+
+    /// Tree sizes to benchmark
+    #[arg(long, value_delimiter = ',', default_values_t = SIZES)]
+    sizes: Vec<usize>,
+
+    /// Skip tree sizes smaller than this
+    #[arg(long)]
+    min_size: Option<usize>,
+
+    /// Skip tree sizes larger than this
+    #[arg(long)]
+    max_size: Option<usize>,
+
+    /// Result counts for nearest-n and best-n-within. Each value runs as its own group,
+    /// unless sweeping. Defaults to 10, or 1,2,5,10,20,50,100,500,1000 with --sweep.
+    #[arg(long, value_delimiter = ',')]
+    k: Vec<usize>,
+
+    /// Squared radii for the radius-based queries. Defaults to each query's usual radius,
+    /// or 0.0001 to 0.05 with --sweep. More than one needs --sweep.
+    #[arg(long, value_delimiter = ',')]
+    radius: Vec<f64>,
+
+    /// Sweep k and the radius: each query's results for every value are put in one group,
+    /// eg `Query Nearest n`, with the value tagged in benchmark ids, eg ` k=50`
+    #[arg(long)]
+    sweep: bool,
+
+    /// Operation mixes for mixed-workload, as <add>:<nearest_n>:<remove>, eg 50:40:10.
+    /// Each mix runs separately.
+    #[arg(long, value_delimiter = ',', default_values_t = WORKLOAD_MIXES)]
+    mix: Vec<OperationMix>,
+
+    /// Percentages of each tree's points to remove, for remove and nearest-n-after-remove
+    #[arg(long, value_delimiter = ',', default_values_t = REMOVAL_PERCENTAGES)]
+    remove_percent: Vec<usize>,
+
+    /// Orders to remove points in, for remove and nearest-n-after-remove
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = REMOVAL_ORDERS)]
+    removal_order: Vec<RemovalOrder>,
+
+    /// Number of samples criterion collects per benchmark
+    #[arg(long)]
+    sample_size: Option<usize>,
+
+    /// Criterion measurement time per benchmark, in seconds
+    #[arg(long)]
+    measurement_time: Option<u64>,
+
+    /// Directory that criterion writes its results to
+    #[arg(long, default_value = "target/criterion")]
+    criterion_dir: PathBuf,
+
+    /// File to write the NDJSON results to
+    #[arg(long, short, default_value = "all-benchmarks.ndjson")]
+    output: PathBuf,
+}
+
+impl Args {
+    /// Tree sizes to run in `dims` dimensions. Above 4 dimensions, the largest sizes are
+    /// dropped so that runs finish in reasonable time.
+    fn selected_sizes(&self, dims: usize) -> Vec<usize> {
+        self.sizes
+            .iter()
+            .copied()
+            .filter(|&size| size_within_dims_cap(dims, size))
+            .filter(|&size| self.min_size.is_none_or(|min| size >= min))
+            .filter(|&size| self.max_size.is_none_or(|max| size <= max))
+            .collect()
+    }
+
+    /// Candidate list sizes to run a library with. Exact libraries ignore them, so only run once.
+    fn ef_values(&self, library: Library) -> Vec<usize> {
+        if library.is_approximate() {
+            self.ef.clone()
+        } else {
+            vec![ef_search()]
+        }
+    }
+
+    /// Result counts to run, from --k or --sweep
+    fn ks(&self) -> Vec<usize> {
+        if !self.k.is_empty() {
+            self.k.clone()
+        } else if self.sweep {
+            K_SWEEP.to_vec()
+        } else {
+            vec![10]
+        }
+    }
+
+    /// Radii to run, from --radius or --sweep. `None` runs each query's usual radius.
+    fn radii(&self) -> Vec<Option<f64>> {
+        if !self.radius.is_empty() {
+            self.radius.iter().copied().map(Some).collect()
+        } else if self.sweep {
+            RADIUS_SWEEP.into_iter().map(Some).collect()
+        } else {
+            vec![None]
+        }
+    }
+
+    /// Leaf sizes to run a library with. Libraries that fix theirs at compile time, or
+    /// that do not have one, ignore them, so only run once.
+    fn leaf_sizes(&self, library: Library) -> Vec<usize> {
+        if library.has_runtime_leaf_size() && !self.leaf_size.is_empty() {
+            self.leaf_size.clone()
+        } else {
+            vec![leaf_size()]
+        }
+    }
+
+    /// Every combination of candidate list size and leaf size to run a library with
+    fn ef_and_leaf_sizes(&self, library: Library) -> Vec<(usize, usize)> {
+        let leaf_sizes = self.leaf_sizes(library);
+
+        self.ef_values(library)
+            .into_iter()
+            .flat_map(|ef| leaf_sizes.iter().map(move |&leaf| (ef, leaf)))
+            .collect()
+    }
+
+    /// Every combination of the requested removal percentages and orders
+    fn removals(&self) -> Vec<Removal> {
+        self.remove_percent
+            .iter()
+            .flat_map(|&percent| {
+                self.removal_order
+                    .iter()
+                    .map(move |&order| Removal { percent, order })
+            })
+            .collect()
+    }
+
+    /// Each query paired with the parameters it should run with. Only queries that
+    /// take a `k` are repeated for each requested value of it, only queries that take a radius
+    /// for each radius, only the mixed workload for each operation mix, and only the removal
+    /// queries for each removal.
+    fn query_params(&self) -> Vec<(Query, QueryParams)> {
+        let mut runs = vec![];
+        for &query in &self.query {
+            let ks = if query.takes_k() {
+                self.ks()
+            } else {
+                self.ks().into_iter().take(1).collect()
+            };
+            let radii = if query.takes_radius() {
+                self.radii()
+            } else {
+                vec![None]
+            };
+            let mixes = match query {
+                Query::MixedWorkload => self.mix.clone(),
+                _ => vec![self.mix[0]],
+            };
+            let removals = match query {
+                Query::Remove | Query::NearestNAfterRemove => self.removals(),
+                _ => self.removals().into_iter().take(1).collect(),
+            };
+            for k in ks {
+                for &radius in &radii {
+                    for &mix in &mixes {
+                        for &removal in &removals {
+                            runs.push((
+                                query,
+                                QueryParams {
+                                    k,
+                                    radius,
+                                    mix,
+                                    removal,
+                                    sweep: self.sweep,
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        runs
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    pretty_env_logger::init();
+    let args = Args::parse();
+    if args.radius.len() > 1 && !args.sweep {
+        return Err("more than one --radius needs --sweep, to tell their results apart".into());
+    }
+    if args.memory && !cfg!(feature = "memory") {
+        return Err("--memory needs kdbench to be built with the memory feature".into());
+    }
+
+    set_distributions(Distributions {
+        points: args.distribution,
+        queries: args.query_distribution.unwrap_or(args.distribution),
+    })
+    .expect("distributions are set before any dataset is loaded");
+
+    if let Some(points_file) = &args.points_file {
+        let points = PointFile::new(points_file, args.file_dims)?;
+        log::info!(
+            "{} holds {} {}D points",
+            points_file.display(),
+            points.len()?,
+            points.dims()?
+        );
+        let queries = args
+            .queries_file
+            .as_ref()
+            .map(|queries_file| PointFile::new(queries_file, args.file_dims))
+            .transpose()?;
+
+        set_point_files(PointFiles { points, queries })
+            .expect("point files are set before any dataset is loaded");
+    }
+
+    let mut criterion = Criterion::default().output_directory(&args.criterion_dir);
+    if let Some(sample_size) = args.sample_size {
+        criterion = criterion.sample_size(sample_size);
+    }
+    if let Some(measurement_time) = args.measurement_time {
+        criterion = criterion.measurement_time(Duration::from_secs(measurement_time));
+    }
+
+    set_tag_leaf_sizes(!args.leaf_size.is_empty());
+
+    let mut output = BufWriter::new(File::create(&args.output)?);
+
+    for (query, params) in args.query_params() {
+        let group_name = query.group_name(&params);
+        let started = SystemTime::now();
+
+        let mut group = criterion.benchmark_group(&group_name);
+        let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
+        group.plot_config(plot_config);
+
+        for &library in &args.library {
+            for (ef, leaf) in args.ef_and_leaf_sizes(library) {
+                set_ef_search(ef);
+                set_leaf_size(leaf);
+                for &scalar in &args.scalar {
+                    for &dims in &args.dims {
+                        for size in args.selected_sizes(dims) {
+                            if !dataset_available(dims, size) {
+                                log::info!(
+                                    "Skipping {}D size {}: not available from the point files",
+                                    dims,
+                                    size
+                                );
+                                continue;
+                            }
+                            if let Some(throughput) = query.throughput(size, &params) {
+                                group.throughput(Throughput::Elements(throughput as u64));
+                            }
+                            if !run_matrix_point(
+                                &mut group, library, scalar, dims, query, size, &params,
+                            ) {
+                                log::info!(
+                                    "Skipping {:?} {}D {:?} for {:?}: \
+                                     unsupported combination or library not enabled",
+                                    library,
+                                    dims,
+                                    scalar,
+                                    query
+                                );
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        group.finish();
+
+        let records = collect_benchmark_records(&args.criterion_dir, started)?;
+        let benchmarks = records.iter().map(|record| record.id.clone()).collect();
+        for record in records {
+            write_message(&mut output, &Message::BenchmarkComplete(Box::new(record)))?;
+        }
+
+        let report_directory = args.criterion_dir.join("reports").join(&group_name);
+        write_message(
+            &mut output,
+            &Message::GroupComplete(GroupComplete {
+                group_name,
+                benchmarks,
+                report_directory: report_directory.to_string_lossy().into_owned(),
+            }),
+        )?;
+        output.flush()?;
+    }
+
+    if args.memory {
+        for &library in &args.library {
+            for leaf in args.leaf_sizes(library) {
+                set_leaf_size(leaf);
+                for &scalar in &args.scalar {
+                    for &dims in &args.dims {
+                        for size in args.selected_sizes(dims) {
+                            if !dataset_available(dims, size) {
+                                continue;
+                            }
+                            let Some(memory) =
+                                visit_matrix_point(library, scalar, dims, MemoryRun { size })
+                            else {
+                                log::info!(
+                                    "Skipping memory for {:?} {}D {:?}: \
+                                     unsupported combination or library not enabled",
+                                    library,
+                                    dims,
+                                    scalar
+                                );
+                                break;
+                            };
+
+                            log::info!(
+                                "{}: {} bytes retained, {} bytes peak",
+                                memory.id,
+                                memory.retained_bytes,
+                                memory.peak_bytes
+                            );
+                            write_message(&mut output, &Message::MemoryComplete(memory))?;
+                        }
+                    }
+                }
+            }
+        }
+        output.flush()?;
+    }
+
+    if args.recall {
+        for k in args.ks() {
+            for &scalar in &args.scalar {
+                for &dims in &args.dims {
+                    for size in args.selected_sizes(dims) {
+                        if !dataset_available(dims, size) {
+                            continue;
+                        }
+                        let reference = ExactNeighbours { size, k };
+                        let Some(exact) =
+                            visit_matrix_point(args.recall_reference, scalar, dims, reference)
+                                .flatten()
+                        else {
+                            log::info!(
+                                "Skipping recall for {}D {:?}: {:?} can not be used as the reference",
+                                dims,
+                                scalar,
+                                args.recall_reference
+                            );
+                            continue;
+                        };
+
+                        for &library in &args.library {
+                            for ef in args.ef_values(library) {
+                                set_ef_search(ef);
+                                let run = RecallRun {
+                                    size,
+                                    k,
+                                    exact: &exact,
+                                };
+                                let Some(recall) =
+                                    visit_matrix_point(library, scalar, dims, run).flatten()
+                                else {
+                                    log::info!(
+                                        "Skipping recall for {:?} {}D {:?}: \
+                                         unsupported combination or library not enabled",
+                                        library,
+                                        dims,
+                                        scalar
+                                    );
+                                    break;
+                                };
+
+                                log::info!(
+                                    "{}: recall {:.4}, built in {:.3}s, {:.0} queries/s",
+                                    recall.id,
+                                    recall.recall,
+                                    recall.build_secs,
+                                    recall.queries_per_sec
+                                );
+                                write_message(&mut output, &Message::RecallComplete(recall))?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        output.flush()?;
+    }
+
+    Ok(())
+}
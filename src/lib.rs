@@ -1,16 +1,17 @@
+pub mod adapters;
+pub mod codegen;
+pub mod compare_output;
+pub mod dataset;
+pub mod diff;
+pub mod distribution;
+pub mod matrix;
+pub mod memory;
+pub mod ndjson;
+pub mod point_file;
+pub mod recall;
+pub mod results;
+pub mod scalar;
+pub mod scenarios;
 pub mod utils;
-
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub mod variants;
+pub mod workload;
@@ -0,0 +1,425 @@
+//! Runtime selection of a slice of the scenario matrix: which libraries, queries,
+//! dimensions, scalar types and tree sizes to run.
+
+use clap::ValueEnum;
+use criterion::measurement::WallTime;
+use criterion::BenchmarkGroup;
+
+#[cfg(feature = "fnntw")]
+use crate::adapters::Fnntw;
+#[cfg(feature = "hnsw_rs")]
+use crate::adapters::HnswRs;
+#[cfg(feature = "instant-distance")]
+use crate::adapters::InstantDistance;
+#[cfg(feature = "kd-tree")]
+use crate::adapters::KdTree;
+#[cfg(feature = "kdtree")]
+use crate::adapters::Kdtree;
+#[cfg(feature = "kiddo_v1")]
+use crate::adapters::KiddoV1;
+#[cfg(feature = "nabo")]
+use crate::adapters::Nabo;
+#[cfg(feature = "petal-neighbors")]
+use crate::adapters::PetalBallTree;
+#[cfg(feature = "rstar")]
+use crate::adapters::Rstar;
+use crate::adapters::{BruteForce, KdTreeAdapter, Operation};
+#[cfg(feature = "kiddo_next")]
+use crate::adapters::{KiddoNext, KiddoNextImmutable};
+#[cfg(feature = "kiddo_v2")]
+use crate::adapters::{KiddoV2, KiddoV2Fixed};
+#[cfg(feature = "kiddo_v3")]
+use crate::adapters::{KiddoV3, KiddoV3Fixed, KiddoV3Immutable};
+#[cfg(feature = "kiddo_v5")]
+use crate::adapters::{KiddoV5, KiddoV5Fixed, KiddoV5Immutable};
+use crate::scalar::Scalar;
+#[cfg(any(feature = "kiddo_v2", feature = "kiddo_v3", feature = "kiddo_v5"))]
+use crate::scalar::FXP;
+use crate::scenarios::{
+    bench_add_to_empty, bench_best_n_within, bench_mixed_workload, bench_nearest_n,
+    bench_nearest_n_after_remove, bench_nearest_n_within, bench_nearest_one, bench_remove,
+    bench_within, bench_within_unsorted, with_parameter_tag, BEST_N_RADIUS, QUERY_POINTS_PER_LOOP,
+    RADIUS, RADIUS_QUERY_POINTS_PER_LOOP, WORKLOAD_OPS_PER_LOOP,
+};
+use crate::workload::{OperationMix, Removal};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Library {
+    KiddoV1,
+    KiddoV2,
+    KiddoV3Std,
+    KiddoV3Immutable,
+    KiddoV5Std,
+    KiddoV5Immutable,
+    KiddoNextStd,
+    KiddoNextImmutable,
+    Fnntw,
+    Nabo,
+    Rstar,
+    Kdtree,
+    KdTree,
+    PetalBallTree,
+    HnswRs,
+    InstantDistance,
+    BruteForce,
+}
+
+impl Library {
+    /// Whether the library is an approximate index, whose results depend on
+    /// [`ef_search`](crate::adapters::ef_search)
+    pub fn is_approximate(&self) -> bool {
+        matches!(self, Library::HnswRs | Library::InstantDistance)
+    }
+
+    /// Whether the library's leaf size is taken from
+    /// [`leaf_size`](crate::adapters::leaf_size) when a tree is built. kiddo v2 onwards fix
+    /// theirs at compile time, to [`BUCKET_SIZE`](crate::adapters::BUCKET_SIZE).
+    pub fn has_runtime_leaf_size(&self) -> bool {
+        matches!(
+            self,
+            Library::KiddoV1 | Library::Fnntw | Library::Nabo | Library::Kdtree
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ScalarType {
+    F32,
+    F64,
+    Fxp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Query {
+    AddToEmpty,
+    Remove,
+    NearestOne,
+    NearestN,
+    Within,
+    WithinUnsorted,
+    NearestNWithin,
+    NearestNWithinUnsorted,
+    BestNWithin,
+    MixedWorkload,
+    NearestNAfterRemove,
+}
+
+/// Query parameters shared by every benchmark in a run
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QueryParams {
+    /// Result count for nearest_n and best_n_within
+    pub k: usize,
+
+    /// Squared radius for the radius-based queries. Each query's usual radius is used if unset.
+    pub radius: Option<f64>,
+
+    /// Proportions of adds, nearest_n queries and removes for the mixed workload
+    pub mix: OperationMix,
+
+    /// Points removed by remove and nearest-n-after-remove
+    pub removal: Removal,
+
+    /// Whether `k` and the radius are being swept. If so, they are tagged in benchmark ids,
+    /// eg ` k=50` or ` radius=0.001`, rather than `k` being part of the group name, so that
+    /// every value of them is in the same group.
+    pub sweep: bool,
+}
+
+impl Query {
+    /// The adapter operations that a query needs, beyond construction
+    pub fn operations(&self) -> &'static [Operation] {
+        match self {
+            Query::AddToEmpty => &[],
+            Query::Remove => &[Operation::Remove],
+            Query::NearestOne => &[Operation::NearestOne],
+            Query::NearestN => &[Operation::NearestN],
+            Query::Within => &[Operation::Within],
+            Query::WithinUnsorted => &[Operation::WithinUnsorted],
+            Query::NearestNWithin | Query::NearestNWithinUnsorted => &[Operation::NearestNWithin],
+            Query::BestNWithin => &[Operation::BestNWithin],
+            Query::MixedWorkload => &[Operation::Add, Operation::NearestN, Operation::Remove],
+            Query::NearestNAfterRemove => &[Operation::Remove, Operation::NearestN],
+        }
+    }
+
+    /// Whether the query takes a result count, `k`
+    pub fn takes_k(&self) -> bool {
+        matches!(
+            self,
+            Query::NearestN | Query::BestNWithin | Query::NearestNAfterRemove
+        )
+    }
+
+    /// Whether the query takes a radius
+    pub fn takes_radius(&self) -> bool {
+        matches!(
+            self,
+            Query::Within
+                | Query::WithinUnsorted
+                | Query::NearestNWithin
+                | Query::NearestNWithinUnsorted
+                | Query::BestNWithin
+        )
+    }
+
+    /// Criterion group name, matching the names used by the benches
+    pub fn group_name(&self, params: &QueryParams) -> String {
+        match self {
+            Query::NearestN if params.sweep => "Query Nearest n".to_string(),
+            Query::BestNWithin if params.sweep => "Query Best n".to_string(),
+            Query::NearestNAfterRemove if params.sweep => {
+                "Query Nearest n after Remove".to_string()
+            }
+            Query::AddToEmpty => "Add to Empty Tree".to_string(),
+            Query::Remove => "Remove from Tree".to_string(),
+            Query::NearestOne => "Query Nearest 1".to_string(),
+            Query::NearestN => format!("Query Nearest {}", params.k),
+            Query::Within => "Query within radius".to_string(),
+            Query::WithinUnsorted => "Query within radius unsorted".to_string(),
+            Query::NearestNWithin => "Query nearest n within radius".to_string(),
+            Query::NearestNWithinUnsorted => "Query nearest n within radius unsorted".to_string(),
+            Query::BestNWithin => format!("Query Best {}", params.k),
+            Query::MixedWorkload => "Mixed Workload".to_string(),
+            Query::NearestNAfterRemove => format!("Query Nearest {} after Remove", params.k),
+        }
+    }
+
+    /// Number of queries performed per iteration on a tree of `size` points, if the query is
+    /// throughput-measured. For Remove, it is the number of points removed.
+    pub fn throughput(&self, size: usize, params: &QueryParams) -> Option<usize> {
+        match self {
+            Query::AddToEmpty => None,
+            Query::Remove => Some(params.removal.qty(size)),
+            Query::NearestOne
+            | Query::NearestN
+            | Query::BestNWithin
+            | Query::NearestNAfterRemove => Some(QUERY_POINTS_PER_LOOP),
+            Query::Within
+            | Query::WithinUnsorted
+            | Query::NearestNWithin
+            | Query::NearestNWithinUnsorted => Some(RADIUS_QUERY_POINTS_PER_LOOP),
+            Query::MixedWorkload => Some(WORKLOAD_OPS_PER_LOOP),
+        }
+    }
+}
+
+/// Something to do with one point of the matrix, once its runtime selection of library,
+/// scalar type and dimensions has been resolved to a concrete adapter type
+pub trait MatrixVisitor {
+    type Output;
+
+    fn visit<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(self) -> Self::Output;
+}
+
+macro_rules! visit_dims {
+    ($adapter:ident, $a:ty, $dims:expr, $visitor:expr) => {
+        match $dims {
+            2 => Some($visitor.visit::<$a, $adapter<$a, 2>, 2>()),
+            3 => Some($visitor.visit::<$a, $adapter<$a, 3>, 3>()),
+            4 => Some($visitor.visit::<$a, $adapter<$a, 4>, 4>()),
+            6 => Some($visitor.visit::<$a, $adapter<$a, 6>, 6>()),
+            8 => Some($visitor.visit::<$a, $adapter<$a, 8>, 8>()),
+            12 => Some($visitor.visit::<$a, $adapter<$a, 12>, 12>()),
+            16 => Some($visitor.visit::<$a, $adapter<$a, 16>, 16>()),
+            32 => Some($visitor.visit::<$a, $adapter<$a, 32>, 32>()),
+            64 => Some($visitor.visit::<$a, $adapter<$a, 64>, 64>()),
+            _ => None,
+        }
+    };
+}
+
+/// Dispatches the runtime selection of library, scalar type and dimensions to the
+/// visitor, monomorphised for the corresponding adapter.
+///
+/// Returns `None` if the library does not support that combination, or was not enabled
+/// as a cargo feature.
+pub fn visit_matrix_point<V: MatrixVisitor>(
+    library: Library,
+    scalar: ScalarType,
+    dims: usize,
+    visitor: V,
+) -> Option<V::Output> {
+    match (library, scalar) {
+        #[cfg(feature = "kiddo_v1")]
+        (Library::KiddoV1, ScalarType::F32) => visit_dims!(KiddoV1, f32, dims, visitor),
+        #[cfg(feature = "kiddo_v1")]
+        (Library::KiddoV1, ScalarType::F64) => visit_dims!(KiddoV1, f64, dims, visitor),
+        #[cfg(feature = "kiddo_v2")]
+        (Library::KiddoV2, ScalarType::F32) => visit_dims!(KiddoV2, f32, dims, visitor),
+        #[cfg(feature = "kiddo_v2")]
+        (Library::KiddoV2, ScalarType::F64) => visit_dims!(KiddoV2, f64, dims, visitor),
+        #[cfg(feature = "kiddo_v2")]
+        (Library::KiddoV2, ScalarType::Fxp) => visit_dims!(KiddoV2Fixed, FXP, dims, visitor),
+        #[cfg(feature = "kiddo_v3")]
+        (Library::KiddoV3Std, ScalarType::F32) => visit_dims!(KiddoV3, f32, dims, visitor),
+        #[cfg(feature = "kiddo_v3")]
+        (Library::KiddoV3Std, ScalarType::F64) => visit_dims!(KiddoV3, f64, dims, visitor),
+        #[cfg(feature = "kiddo_v3")]
+        (Library::KiddoV3Std, ScalarType::Fxp) => visit_dims!(KiddoV3Fixed, FXP, dims, visitor),
+        #[cfg(feature = "kiddo_v3")]
+        (Library::KiddoV3Immutable, ScalarType::F32) => {
+            visit_dims!(KiddoV3Immutable, f32, dims, visitor)
+        }
+        #[cfg(feature = "kiddo_v3")]
+        (Library::KiddoV3Immutable, ScalarType::F64) => {
+            visit_dims!(KiddoV3Immutable, f64, dims, visitor)
+        }
+        #[cfg(feature = "kiddo_v5")]
+        (Library::KiddoV5Std, ScalarType::F32) => visit_dims!(KiddoV5, f32, dims, visitor),
+        #[cfg(feature = "kiddo_v5")]
+        (Library::KiddoV5Std, ScalarType::F64) => visit_dims!(KiddoV5, f64, dims, visitor),
+        #[cfg(feature = "kiddo_v5")]
+        (Library::KiddoV5Std, ScalarType::Fxp) => visit_dims!(KiddoV5Fixed, FXP, dims, visitor),
+        #[cfg(feature = "kiddo_v5")]
+        (Library::KiddoV5Immutable, ScalarType::F32) => {
+            visit_dims!(KiddoV5Immutable, f32, dims, visitor)
+        }
+        #[cfg(feature = "kiddo_v5")]
+        (Library::KiddoV5Immutable, ScalarType::F64) => {
+            visit_dims!(KiddoV5Immutable, f64, dims, visitor)
+        }
+        #[cfg(feature = "kiddo_next")]
+        (Library::KiddoNextStd, ScalarType::F32) => visit_dims!(KiddoNext, f32, dims, visitor),
+        #[cfg(feature = "kiddo_next")]
+        (Library::KiddoNextStd, ScalarType::F64) => visit_dims!(KiddoNext, f64, dims, visitor),
+        #[cfg(feature = "kiddo_next")]
+        (Library::KiddoNextImmutable, ScalarType::F32) => {
+            visit_dims!(KiddoNextImmutable, f32, dims, visitor)
+        }
+        #[cfg(feature = "kiddo_next")]
+        (Library::KiddoNextImmutable, ScalarType::F64) => {
+            visit_dims!(KiddoNextImmutable, f64, dims, visitor)
+        }
+        #[cfg(feature = "fnntw")]
+        (Library::Fnntw, ScalarType::F64) => visit_dims!(Fnntw, f64, dims, visitor),
+        #[cfg(feature = "nabo")]
+        (Library::Nabo, ScalarType::F32) => visit_dims!(Nabo, f32, dims, visitor),
+        #[cfg(feature = "nabo")]
+        (Library::Nabo, ScalarType::F64) => visit_dims!(Nabo, f64, dims, visitor),
+        #[cfg(feature = "rstar")]
+        (Library::Rstar, ScalarType::F32) => visit_dims!(Rstar, f32, dims, visitor),
+        #[cfg(feature = "rstar")]
+        (Library::Rstar, ScalarType::F64) => visit_dims!(Rstar, f64, dims, visitor),
+        #[cfg(feature = "kdtree")]
+        (Library::Kdtree, ScalarType::F32) => visit_dims!(Kdtree, f32, dims, visitor),
+        #[cfg(feature = "kdtree")]
+        (Library::Kdtree, ScalarType::F64) => visit_dims!(Kdtree, f64, dims, visitor),
+        #[cfg(feature = "kd-tree")]
+        (Library::KdTree, ScalarType::F32) => visit_dims!(KdTree, f32, dims, visitor),
+        #[cfg(feature = "kd-tree")]
+        (Library::KdTree, ScalarType::F64) => visit_dims!(KdTree, f64, dims, visitor),
+        #[cfg(feature = "petal-neighbors")]
+        (Library::PetalBallTree, ScalarType::F32) => {
+            visit_dims!(PetalBallTree, f32, dims, visitor)
+        }
+        #[cfg(feature = "petal-neighbors")]
+        (Library::PetalBallTree, ScalarType::F64) => {
+            visit_dims!(PetalBallTree, f64, dims, visitor)
+        }
+        #[cfg(feature = "hnsw_rs")]
+        (Library::HnswRs, ScalarType::F32) => visit_dims!(HnswRs, f32, dims, visitor),
+        #[cfg(feature = "hnsw_rs")]
+        (Library::HnswRs, ScalarType::F64) => visit_dims!(HnswRs, f64, dims, visitor),
+        #[cfg(feature = "instant-distance")]
+        (Library::InstantDistance, ScalarType::F32) => {
+            visit_dims!(InstantDistance, f32, dims, visitor)
+        }
+        #[cfg(feature = "instant-distance")]
+        (Library::InstantDistance, ScalarType::F64) => {
+            visit_dims!(InstantDistance, f64, dims, visitor)
+        }
+        (Library::BruteForce, ScalarType::F32) => visit_dims!(BruteForce, f32, dims, visitor),
+        (Library::BruteForce, ScalarType::F64) => visit_dims!(BruteForce, f64, dims, visitor),
+        _ => None,
+    }
+}
+
+/// Runs one query for one adapter at one tree size
+pub struct ScenarioRun<'a, 'g> {
+    pub group: &'a mut BenchmarkGroup<'g, WallTime>,
+    pub query: Query,
+    pub size: usize,
+    pub params: &'a QueryParams,
+}
+
+impl MatrixVisitor for ScenarioRun<'_, '_> {
+    /// `false` if nothing was run because the adapter does not support the query
+    type Output = bool;
+
+    fn visit<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(self) -> bool {
+        let ScenarioRun {
+            group,
+            query,
+            size,
+            params,
+        } = self;
+
+        if !query.operations().iter().all(|&op| T::supports(op)) {
+            return false;
+        }
+        if query.takes_k() && !T::supports_qty(params.k) {
+            return false;
+        }
+
+        let radius = params.radius.unwrap_or(match query {
+            Query::BestNWithin => BEST_N_RADIUS,
+            _ => RADIUS,
+        });
+
+        let mut run = || match query {
+            Query::AddToEmpty => bench_add_to_empty::<A, T, K>(group, size),
+            Query::Remove => bench_remove::<A, T, K>(group, size, params.removal),
+            Query::NearestOne => bench_nearest_one::<A, T, K>(group, size),
+            Query::NearestN => bench_nearest_n::<A, T, K>(group, size, params.k),
+            Query::Within => bench_within::<A, T, K>(group, size, radius),
+            Query::WithinUnsorted => bench_within_unsorted::<A, T, K>(group, size, radius),
+            Query::NearestNWithin => bench_nearest_n_within::<A, T, K>(group, size, radius, true),
+            Query::NearestNWithinUnsorted => {
+                bench_nearest_n_within::<A, T, K>(group, size, radius, false)
+            }
+            Query::BestNWithin => bench_best_n_within::<A, T, K>(group, size, radius, params.k),
+            Query::MixedWorkload => bench_mixed_workload::<A, T, K>(group, size, params.mix),
+            Query::NearestNAfterRemove => {
+                bench_nearest_n_after_remove::<A, T, K>(group, size, params.removal, params.k)
+            }
+        };
+
+        let run_with_radius = || {
+            if params.sweep && query.takes_radius() {
+                with_parameter_tag("radius", radius, run)
+            } else {
+                run()
+            }
+        };
+        if params.sweep && query.takes_k() {
+            with_parameter_tag("k", params.k, run_with_radius)
+        } else {
+            run_with_radius()
+        }
+
+        true
+    }
+}
+
+/// Runs one point of the matrix.
+///
+/// Returns `false` if the library does not support that combination, or was not enabled
+/// as a cargo feature.
+pub fn run_matrix_point(
+    group: &mut BenchmarkGroup<WallTime>,
+    library: Library,
+    scalar: ScalarType,
+    dims: usize,
+    query: Query,
+    size: usize,
+    params: &QueryParams,
+) -> bool {
+    let run = ScenarioRun {
+        group,
+        query,
+        size,
+        params,
+    };
+
+    visit_matrix_point(library, scalar, dims, run).unwrap_or(false)
+}
@@ -0,0 +1,131 @@
+//! Memory footprint of each library's trees, measured with a counting global allocator.
+//!
+//! Binaries that measure memory install [`CountingAllocator`] as their global allocator.
+//! It only counts while a measurement is in progress, but still checks whether it is counting
+//! on every allocation, so kdbench only installs it with the `memory` feature.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::adapters::{adapter_tags, KdTreeAdapter};
+use crate::dataset::{dataset_points, dataset_tags};
+use crate::matrix::MatrixVisitor;
+use crate::ndjson::MemoryComplete;
+use crate::scalar::Scalar;
+use crate::utils::dims_and_type;
+
+/// Criterion-style group name used in the ids of memory results
+pub const MEMORY_GROUP_NAME: &str = "Tree Memory";
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator, keeping track of the bytes currently allocated
+/// and the peak reached while counting is enabled.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && COUNTING.load(Ordering::Relaxed) {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if COUNTING.load(Ordering::Relaxed) {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && COUNTING.load(Ordering::Relaxed) {
+            if new_size > layout.size() {
+                let grown = new_size - layout.size();
+                let current = CURRENT.fetch_add(grown, Ordering::Relaxed) + grown;
+                PEAK.fetch_max(current, Ordering::Relaxed);
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+
+        new_ptr
+    }
+}
+
+/// Bytes used by one tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// Bytes still allocated once construction has finished
+    pub retained_bytes: usize,
+
+    /// Most bytes allocated at any one time during construction
+    pub peak_bytes: usize,
+}
+
+/// Measures the memory allocated while building a tree of type `T` from `points`.
+///
+/// Only allocations made between the start and end of construction are counted,
+/// so `points` themselves are excluded. Measurements are only meaningful if
+/// [`CountingAllocator`] is the global allocator and nothing else is allocating concurrently.
+pub fn measure_construction<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    points: &[[A; K]],
+) -> MemoryFootprint {
+    // Deallocations of memory allocated before counting started would otherwise
+    // underflow the counter, so counting starts from an offset
+    const OFFSET: usize = usize::MAX / 2;
+
+    CURRENT.store(OFFSET, Ordering::SeqCst);
+    PEAK.store(OFFSET, Ordering::SeqCst);
+    COUNTING.store(true, Ordering::SeqCst);
+
+    let tree = T::build_from_slice(points);
+
+    COUNTING.store(false, Ordering::SeqCst);
+    let footprint = MemoryFootprint {
+        retained_bytes: CURRENT.load(Ordering::SeqCst).saturating_sub(OFFSET),
+        peak_bytes: PEAK.load(Ordering::SeqCst) - OFFSET,
+    };
+    drop(tree);
+
+    footprint
+}
+
+/// Measures the footprint of one point of the matrix, over the shared dataset
+pub struct MemoryRun {
+    pub size: usize,
+}
+
+impl MatrixVisitor for MemoryRun {
+    type Output = MemoryComplete;
+
+    fn visit<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(self) -> MemoryComplete {
+        let points = dataset_points::<A, K>(self.size);
+        let footprint = measure_construction::<A, T, K>(&points);
+
+        MemoryComplete {
+            id: format!(
+                "{}/{} {}{}{}/{}",
+                MEMORY_GROUP_NAME,
+                T::NAME,
+                dims_and_type::<A, K>(),
+                adapter_tags::<A, T, K>(),
+                dataset_tags(),
+                self.size
+            ),
+            retained_bytes: footprint.retained_bytes,
+            peak_bytes: footprint.peak_bytes,
+        }
+    }
+}
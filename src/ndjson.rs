@@ -0,0 +1,337 @@
+//! Reading and writing benchmark results in the NDJSON format that
+//! `cargo criterion --message-format json` emits.
+//!
+//! Criterion itself only writes its results as per-benchmark JSON files under its output
+//! directory. [`collect_benchmark_records`] turns those files back into the records that
+//! cargo-criterion would have emitted, so that anything run through `kdbench` produces output
+//! interchangeable with a `cargo criterion` run.
+
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+/// Relative change in the mean below which a difference is treated as noise
+pub const NOISE_THRESHOLD: f64 = 0.01;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    pub estimate: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub unit: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Throughput {
+    pub per_iteration: u64,
+    pub unit: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChangeDetails {
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub change: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkComplete {
+    pub id: String,
+    pub report_directory: String,
+    pub iteration_count: Vec<u64>,
+    pub measured_values: Vec<f64>,
+    pub unit: String,
+    pub throughput: Vec<Throughput>,
+    pub typical: Estimate,
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub median_abs_dev: Estimate,
+    pub slope: Option<Estimate>,
+    pub change: Option<ChangeDetails>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupComplete {
+    pub group_name: String,
+    pub benchmarks: Vec<String>,
+    pub report_directory: String,
+}
+
+/// Memory footprint of one tree. Not emitted by cargo-criterion itself, but written
+/// alongside its messages by `kdbench --memory`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MemoryComplete {
+    pub id: String,
+    pub retained_bytes: usize,
+    pub peak_bytes: usize,
+}
+
+/// Recall of one tree's query results against an exact tree's, with the build time and
+/// query throughput measured alongside it. Written by `kdbench --recall`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecallComplete {
+    pub id: String,
+    pub k: usize,
+
+    /// Fraction of the true `k` nearest neighbours found, averaged over the query points
+    pub recall: f64,
+
+    pub build_secs: f64,
+    pub queries_per_sec: f64,
+}
+
+/// A single line of cargo-criterion's JSON output
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+pub enum Message {
+    BenchmarkComplete(Box<BenchmarkComplete>),
+    GroupComplete(GroupComplete),
+    MemoryComplete(MemoryComplete),
+    RecallComplete(RecallComplete),
+}
+
+/// Reads every message from an NDJSON file, skipping lines that are not criterion messages
+pub fn read_messages(path: &Path) -> Result<Vec<Message>, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Reads the `benchmark-complete` records from an NDJSON file
+pub fn read_benchmarks(path: &Path) -> Result<Vec<BenchmarkComplete>, Box<dyn Error>> {
+    Ok(read_messages(path)?
+        .into_iter()
+        .filter_map(|message| match message {
+            Message::BenchmarkComplete(benchmark) => Some(*benchmark),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Reads the `memory-complete` records from an NDJSON file
+pub fn read_memory(path: &Path) -> Result<Vec<MemoryComplete>, Box<dyn Error>> {
+    Ok(read_messages(path)?
+        .into_iter()
+        .filter_map(|message| match message {
+            Message::MemoryComplete(memory) => Some(memory),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Reads the `recall-complete` records from an NDJSON file
+pub fn read_recall(path: &Path) -> Result<Vec<RecallComplete>, Box<dyn Error>> {
+    Ok(read_messages(path)?
+        .into_iter()
+        .filter_map(|message| match message {
+            Message::RecallComplete(recall) => Some(recall),
+            _ => None,
+        })
+        .collect())
+}
+
+pub fn write_message<W: Write>(writer: &mut W, message: &Message) -> Result<(), Box<dyn Error>> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+// The structs below mirror the files that criterion writes for each benchmark.
+
+#[derive(Deserialize)]
+struct CriterionBenchmark {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+    throughput: Option<CriterionThroughput>,
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+enum CriterionThroughput {
+    Bytes(u64),
+    BytesDecimal(u64),
+    Elements(u64),
+}
+
+#[derive(Deserialize)]
+struct CriterionSample {
+    iters: Vec<f64>,
+    times: Vec<f64>,
+}
+
+#[derive(Deserialize)]
+struct ConfidenceInterval {
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimate {
+    confidence_interval: ConfidenceInterval,
+    point_estimate: f64,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimates {
+    mean: CriterionEstimate,
+    median: CriterionEstimate,
+    median_abs_dev: CriterionEstimate,
+    slope: Option<CriterionEstimate>,
+}
+
+#[derive(Deserialize)]
+struct CriterionChangeEstimates {
+    mean: CriterionEstimate,
+    median: CriterionEstimate,
+}
+
+impl CriterionEstimate {
+    fn to_estimate(&self, unit: &str) -> Estimate {
+        Estimate {
+            estimate: self.point_estimate,
+            lower_bound: self.confidence_interval.lower_bound,
+            upper_bound: self.confidence_interval.upper_bound,
+            unit: unit.to_string(),
+        }
+    }
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, Box<dyn Error>> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Classifies a change the way cargo-criterion reports it, based on whether the
+/// confidence interval of the change in the mean lies entirely outside the noise threshold
+fn classify_change(mean: &Estimate) -> &'static str {
+    if mean.lower_bound > NOISE_THRESHOLD {
+        "Regressed"
+    } else if mean.upper_bound < -NOISE_THRESHOLD {
+        "Improved"
+    } else {
+        "NoChange"
+    }
+}
+
+/// Builds a `benchmark-complete` record from the directory criterion wrote a benchmark's
+/// latest results to, ie the directory containing `new/` and possibly `change/`.
+pub fn benchmark_record(
+    benchmark_dir: &Path,
+    criterion_dir: &Path,
+) -> Result<BenchmarkComplete, Box<dyn Error>> {
+    let new_dir = benchmark_dir.join("new");
+    let benchmark: CriterionBenchmark = read_json(&new_dir.join("benchmark.json"))?;
+    let sample: CriterionSample = read_json(&new_dir.join("sample.json"))?;
+    let estimates: CriterionEstimates = read_json(&new_dir.join("estimates.json"))?;
+
+    let change =
+        read_json::<CriterionChangeEstimates>(&benchmark_dir.join("change/estimates.json"))
+            .ok()
+            .map(|change| {
+                let mean = change.mean.to_estimate("%");
+                ChangeDetails {
+                    change: classify_change(&mean).to_string(),
+                    mean,
+                    median: change.median.to_estimate("%"),
+                }
+            });
+
+    let throughput = benchmark
+        .throughput
+        .map(|throughput| match throughput {
+            CriterionThroughput::Bytes(per_iteration)
+            | CriterionThroughput::BytesDecimal(per_iteration) => Throughput {
+                per_iteration,
+                unit: "bytes".to_string(),
+            },
+            CriterionThroughput::Elements(per_iteration) => Throughput {
+                per_iteration,
+                unit: "elements".to_string(),
+            },
+        })
+        .into_iter()
+        .collect();
+
+    let mean = estimates.mean.to_estimate("ns");
+    let slope = estimates
+        .slope
+        .as_ref()
+        .map(|slope| slope.to_estimate("ns"));
+
+    let report_directory = [
+        Some(benchmark.group_id.as_str()),
+        benchmark.function_id.as_deref(),
+        benchmark.value_str.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .fold(criterion_dir.join("reports"), |path, part| path.join(part));
+
+    Ok(BenchmarkComplete {
+        id: benchmark.full_id,
+        report_directory: report_directory.to_string_lossy().into_owned(),
+        iteration_count: sample.iters.iter().map(|&iters| iters as u64).collect(),
+        measured_values: sample.times,
+        unit: "ns".to_string(),
+        throughput,
+        typical: slope.clone().unwrap_or_else(|| mean.clone()),
+        mean,
+        median: estimates.median.to_estimate("ns"),
+        median_abs_dev: estimates.median_abs_dev.to_estimate("ns"),
+        slope,
+        change,
+    })
+}
+
+fn find_benchmark_dirs(dir: &Path, found: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        if path.join("new").join("benchmark.json").is_file() {
+            found.push(path.clone());
+        }
+
+        if path
+            .file_name()
+            .is_some_and(|name| name != "new" && name != "base")
+        {
+            find_benchmark_dirs(&path, found)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects records for every benchmark under `criterion_dir` whose results were
+/// written at or after `since`, in the order they were run.
+pub fn collect_benchmark_records(
+    criterion_dir: &Path,
+    since: SystemTime,
+) -> Result<Vec<BenchmarkComplete>, Box<dyn Error>> {
+    let mut benchmark_dirs = vec![];
+    if criterion_dir.is_dir() {
+        find_benchmark_dirs(criterion_dir, &mut benchmark_dirs)?;
+    }
+
+    let mut records = vec![];
+    for dir in benchmark_dirs {
+        let modified = fs::metadata(dir.join("new").join("estimates.json"))?.modified()?;
+        if modified >= since {
+            records.push((modified, benchmark_record(&dir, criterion_dir)?));
+        }
+    }
+    records.sort_by_key(|(modified, _)| *modified);
+
+    Ok(records.into_iter().map(|(_, record)| record).collect())
+}
@@ -0,0 +1,178 @@
+//! Compares benchmark results across criterion NDJSON files, matching benchmarks by id.
+//!
+//! Every file after the first is compared against the first:
+//!
+//! ```bash
+//! cargo run --release --bin ndjson-diff -- kiddo-5-immutable-std.ndjson kiddo-5-immutable-mveb.ndjson
+//! ```
+//!
+//! With `--baseline-variant` (or `--baseline-codegen`), the benchmarks of every file are
+//! instead split up by the variant (or codegen profile) that `kdab` tagged them with, and
+//! every other variant is compared against the baseline one.
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use kd_tree_comparison::codegen::CODEGEN_TAG;
+use kd_tree_comparison::diff::{diff_runs, Comparison, CONFIDENCE_LEVEL};
+use kd_tree_comparison::ndjson::{read_benchmarks, BenchmarkComplete};
+use kd_tree_comparison::variants::{split_by_tag, VARIANT_TAG};
+
+#[derive(Parser, Debug)]
+#[command(about = "Compare criterion NDJSON results between runs")]
+struct Args {
+    /// Baseline NDJSON file, followed by one or more files to compare against it.
+    /// With `--baseline-variant` or `--baseline-codegen`, any number of files holding tagged runs.
+    #[arg(required = true, num_args = 1..)]
+    files: Vec<PathBuf>,
+
+    /// Compare the variants tagged by `kdab` against this one, rather than files against the first
+    #[arg(long, conflicts_with = "baseline_codegen")]
+    baseline_variant: Option<String>,
+
+    /// Compare the codegen profiles tagged by `kdab` against this one
+    #[arg(long)]
+    baseline_codegen: Option<String>,
+
+    /// Number of bootstrap resamples used for each speedup's confidence interval
+    #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+    resamples: u64,
+
+    /// Number of wins and of losses to list. All are listed if unset.
+    #[arg(long)]
+    top: Option<usize>,
+
+    /// Also list the benchmarks whose change is within the noise
+    #[arg(long)]
+    all: bool,
+}
+
+fn format_time(ns: f64) -> String {
+    if ns >= 1e9 {
+        format!("{:.2} s", ns / 1e9)
+    } else if ns >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.2} µs", ns / 1e3)
+    } else {
+        format!("{:.2} ns", ns)
+    }
+}
+
+fn print_table<'a>(title: &str, comparisons: impl Iterator<Item = &'a Comparison>) {
+    println!("\n{}", title);
+    println!(
+        "  {:>8}  {:^19}  {:>10}  {:>10}  id",
+        "speedup",
+        format!("{:.0}% CI", CONFIDENCE_LEVEL * 100.0),
+        "baseline",
+        "candidate"
+    );
+    for comparison in comparisons {
+        println!(
+            "  {:>7.3}x  [{:>6.3}x, {:>6.3}x]  {:>10}  {:>10}  {}",
+            comparison.speedup,
+            comparison.lower_bound,
+            comparison.upper_bound,
+            format_time(comparison.baseline),
+            format_time(comparison.candidate),
+            comparison.id
+        );
+    }
+}
+
+fn print_diff(
+    args: &Args,
+    baseline_name: &str,
+    baseline: &[BenchmarkComplete],
+    candidate_name: &str,
+    candidate: &[BenchmarkComplete],
+) {
+    let top = args.top.unwrap_or(usize::MAX);
+    let diff = diff_runs(baseline, candidate, args.resamples as usize);
+
+    println!(
+        "{} vs {}: {} benchmarks matched, {} only in the baseline, {} only in the candidate",
+        candidate_name,
+        baseline_name,
+        diff.comparisons.len(),
+        diff.only_in_baseline.len(),
+        diff.only_in_candidate.len()
+    );
+
+    let (significant, noise): (Vec<_>, Vec<_>) =
+        diff.comparisons.iter().partition(|c| c.significant);
+    let wins = significant.iter().copied().filter(|c| c.speedup > 1.0);
+    let losses = significant
+        .iter()
+        .rev()
+        .copied()
+        .filter(|c| c.speedup < 1.0);
+
+    print_table("Improved:", wins.take(top));
+    print_table("Regressed:", losses.take(top));
+    if args.all {
+        print_table("Within noise:", noise.iter().copied());
+    } else {
+        println!("\n{} benchmarks within noise", noise.len());
+    }
+    println!();
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let baseline_tag = match (&args.baseline_variant, &args.baseline_codegen) {
+        (Some(variant), _) => Some((VARIANT_TAG, variant)),
+        (_, Some(codegen)) => Some((CODEGEN_TAG, codegen)),
+        _ => None,
+    };
+    if let Some((key, baseline_value)) = baseline_tag {
+        let mut benchmarks = vec![];
+        for path in &args.files {
+            benchmarks.extend(read_benchmarks(path)?);
+        }
+
+        let mut runs = split_by_tag(benchmarks, key);
+        let baseline = runs
+            .remove(&Some(baseline_value.clone()))
+            .ok_or_else(|| format!("no benchmarks tagged with {}={}", key, baseline_value))?;
+
+        for (value, candidate) in &runs {
+            let Some(value) = value else {
+                continue;
+            };
+            print_diff(
+                &args,
+                &format!("{}={}", key, baseline_value),
+                &baseline,
+                &format!("{}={}", key, value),
+                candidate,
+            );
+        }
+
+        return Ok(());
+    }
+
+    if args.files.len() < 2 {
+        return Err("expected a baseline file and at least one file to compare against it".into());
+    }
+
+    let baseline_path = &args.files[0];
+    let baseline = read_benchmarks(baseline_path)?;
+
+    for candidate_path in &args.files[1..] {
+        let candidate = read_benchmarks(candidate_path)?;
+        print_diff(
+            &args,
+            &baseline_path.display().to_string(),
+            &baseline,
+            &candidate_path.display().to_string(),
+            &candidate,
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! Converts cargo-criterion NDJSON into the JSON array of structured results that the
+//! webapp loads.
+//!
+//! ```bash
+//! cargo run --release --bin ndjson-to-json -- all-benchmarks.ndjson -o all-benchmarks.json
+//! ```
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+
+use kd_tree_comparison::ndjson::{read_benchmarks, read_memory, read_recall};
+use kd_tree_comparison::results::{BenchmarkResult, MemoryResult, RecallResult};
+
+#[derive(Parser, Debug)]
+#[command(about = "Convert criterion NDJSON into structured JSON for the webapp")]
+struct Args {
+    /// NDJSON files to convert. Results from every file are combined.
+    #[arg(default_value = "all-benchmarks.ndjson")]
+    input: Vec<PathBuf>,
+
+    /// File to write the JSON to
+    #[arg(long, short, default_value = "all-benchmarks.json")]
+    output: PathBuf,
+
+    /// File to write any memory footprint results to, as a separate JSON array
+    #[arg(long)]
+    memory_output: Option<PathBuf>,
+
+    /// File to write any recall results to, as a separate JSON array
+    #[arg(long)]
+    recall_output: Option<PathBuf>,
+}
+
+fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<(), Box<dyn Error>> {
+    let mut output = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut output, value)?;
+    writeln!(output)?;
+    output.flush()?;
+
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    pretty_env_logger::init();
+    let args = Args::parse();
+
+    let mut results = vec![];
+    for input in &args.input {
+        for benchmark in read_benchmarks(input)? {
+            match BenchmarkResult::try_from(benchmark) {
+                Ok(result) => results.push(result),
+                Err(err) => log::warn!("Skipping benchmark: {}", err),
+            }
+        }
+    }
+
+    write_json(&args.output, &results)?;
+    log::info!(
+        "Wrote {} results to {}",
+        results.len(),
+        args.output.display()
+    );
+
+    if let Some(memory_output) = &args.memory_output {
+        let mut memory_results = vec![];
+        for input in &args.input {
+            for memory in read_memory(input)? {
+                match MemoryResult::try_from(memory) {
+                    Ok(result) => memory_results.push(result),
+                    Err(err) => log::warn!("Skipping memory result: {}", err),
+                }
+            }
+        }
+
+        write_json(memory_output, &memory_results)?;
+        log::info!(
+            "Wrote {} memory results to {}",
+            memory_results.len(),
+            memory_output.display()
+        );
+    }
+
+    if let Some(recall_output) = &args.recall_output {
+        let mut recall_results = vec![];
+        for input in &args.input {
+            for recall in read_recall(input)? {
+                match RecallResult::try_from(recall) {
+                    Ok(result) => recall_results.push(result),
+                    Err(err) => log::warn!("Skipping recall result: {}", err),
+                }
+            }
+        }
+
+        write_json(recall_output, &recall_results)?;
+        log::info!(
+            "Wrote {} recall results to {}",
+            recall_results.len(),
+            recall_output.display()
+        );
+    }
+
+    Ok(())
+}
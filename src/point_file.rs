@@ -0,0 +1,704 @@
+//! Point sets read from files, so that the benches can run over real point clouds as
+//! well as generated datasets.
+//!
+//! The format is chosen by file extension:
+//!
+//! * `.npy`: a C-ordered `(N, K)` array of little-endian `f4` or `f8`
+//! * `.csv`, `.txt` or `.xyz`: one point per line, with coordinates separated by commas
+//!   or whitespace. A header line and lines starting with `#` are skipped.
+//! * `.f32` and `.f64`: flat little-endian coordinates with no header, `K` per point.
+//!   Their dimensionality has to be given, as it can not be read from the file.
+//!
+//! NPY and raw binary files are memory-mapped. When their coordinates are of the tree's
+//! scalar type, [`Points`] borrow the whole file in place rather than copying it into
+//! a `Vec`, so that building a tree over 100M points does not first need twice the memory.
+
+use std::any::TypeId;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use memmap::{Mmap, MmapOptions};
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::SeedableRng;
+
+use crate::scalar::Scalar;
+
+pub(crate) const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointFileFormat {
+    Npy,
+    Csv,
+    RawF32,
+    RawF64,
+}
+
+impl PointFileFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "npy" => Some(PointFileFormat::Npy),
+            "csv" | "txt" | "xyz" => Some(PointFileFormat::Csv),
+            "f32" => Some(PointFileFormat::RawF32),
+            "f64" => Some(PointFileFormat::RawF64),
+            _ => None,
+        }
+    }
+}
+
+/// Type of the coordinates stored in a binary file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Coord {
+    F32,
+    F64,
+}
+
+impl Coord {
+    fn size(self) -> usize {
+        match self {
+            Coord::F32 => 4,
+            Coord::F64 => 8,
+        }
+    }
+
+    fn read(self, bytes: &[u8]) -> f64 {
+        match self {
+            Coord::F32 => f32::from_le_bytes(bytes[..4].try_into().unwrap()) as f64,
+            Coord::F64 => f64::from_le_bytes(bytes[..8].try_into().unwrap()),
+        }
+    }
+
+    /// Whether coordinates of this type can be used in place as `A`s
+    fn is<A: Scalar>(self) -> bool {
+        let type_id = match self {
+            Coord::F32 => TypeId::of::<f32>(),
+            Coord::F64 => TypeId::of::<f64>(),
+        };
+
+        cfg!(target_endian = "little") && TypeId::of::<A>() == type_id
+    }
+}
+
+/// Every coordinate in a file
+#[derive(Debug)]
+enum Coords {
+    Mapped {
+        mmap: Mmap,
+        offset: usize,
+        coord: Coord,
+        dims: usize,
+        len: usize,
+    },
+    Parsed {
+        values: Vec<f64>,
+        dims: usize,
+    },
+}
+
+impl Coords {
+    fn dims(&self) -> usize {
+        match self {
+            Coords::Mapped { dims, .. } | Coords::Parsed { dims, .. } => *dims,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Coords::Mapped { len, .. } => *len,
+            Coords::Parsed { values, dims } => values.len() / dims,
+        }
+    }
+
+    fn point<const K: usize>(&self, idx: usize) -> [f64; K] {
+        match self {
+            Coords::Mapped {
+                mmap,
+                offset,
+                coord,
+                ..
+            } => {
+                let start = offset + idx * K * coord.size();
+                std::array::from_fn(|dim| coord.read(&mmap[start + dim * coord.size()..]))
+            }
+            Coords::Parsed { values, .. } => std::array::from_fn(|dim| values[idx * K + dim]),
+        }
+    }
+
+    /// The whole file as a slice of points, if its coordinates can be used in place
+    fn as_slice<A: Scalar, const K: usize>(&self) -> Option<&[[A; K]]> {
+        let Coords::Mapped {
+            mmap,
+            offset,
+            coord,
+            dims,
+            len,
+        } = self
+        else {
+            return None;
+        };
+
+        let ptr = mmap[*offset..].as_ptr();
+        if *dims != K
+            || !coord.is::<A>()
+            || !(ptr as usize).is_multiple_of(std::mem::align_of::<A>())
+        {
+            return None;
+        }
+
+        // SAFETY: the mapping was checked to hold `len` points of `K` coordinates from `offset`
+        // when it was made, they are of type `A` and aligned for it, and the mapping lives as
+        // long as the returned slice
+        Some(unsafe { std::slice::from_raw_parts(ptr as *const [A; K], *len) })
+    }
+}
+
+/// Points read from a file. Either converted into a `Vec`, or borrowed in place from
+/// a memory-mapped file. Derefs to a slice of points either way.
+pub struct Points<A, const K: usize>(Storage<A, K>);
+
+enum Storage<A, const K: usize> {
+    Owned(Vec<[A; K]>),
+    Mapped(Arc<Coords>),
+}
+
+impl<A: Scalar, const K: usize> Deref for Points<A, K> {
+    type Target = [[A; K]];
+
+    fn deref(&self) -> &[[A; K]] {
+        match &self.0 {
+            Storage::Owned(points) => points,
+            Storage::Mapped(coords) => coords
+                .as_slice()
+                .expect("mapped points are only created when usable in place"),
+        }
+    }
+}
+
+impl<A, const K: usize> From<Vec<[A; K]>> for Points<A, K> {
+    fn from(points: Vec<[A; K]>) -> Self {
+        Points(Storage::Owned(points))
+    }
+}
+
+/// A file of points. Its contents are mapped, or parsed, on first use and then kept.
+#[derive(Debug)]
+pub struct PointFile {
+    path: PathBuf,
+    format: PointFileFormat,
+    dims: Option<usize>,
+    coords: OnceLock<Arc<Coords>>,
+}
+
+impl PointFile {
+    /// `dims` is required for raw binary files, and checked against the file for other formats
+    pub fn new(path: impl Into<PathBuf>, dims: Option<usize>) -> io::Result<Self> {
+        let path = path.into();
+        let format = PointFileFormat::from_path(&path).ok_or_else(|| {
+            invalid_input(format!(
+                "unrecognised point file {}: expected a .npy, .csv, .f32 or .f64 file",
+                path.display()
+            ))
+        })?;
+
+        if dims.is_none() && matches!(format, PointFileFormat::RawF32 | PointFileFormat::RawF64) {
+            return Err(invalid_input(format!(
+                "the dimensions of raw binary file {} must be given",
+                path.display()
+            )));
+        }
+        if dims == Some(0) {
+            return Err(invalid_input(format!(
+                "the dimensions of point file {} must be at least 1",
+                path.display()
+            )));
+        }
+
+        Ok(PointFile {
+            path,
+            format,
+            dims,
+            coords: OnceLock::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn format(&self) -> PointFileFormat {
+        self.format
+    }
+
+    /// Name of the file as it appears in benchmark ids: its stem, with any spaces replaced
+    pub fn name(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().replace(' ', "_"))
+            .unwrap_or_default()
+    }
+
+    pub fn dims(&self) -> io::Result<usize> {
+        Ok(self.coords()?.dims())
+    }
+
+    /// Number of points in the file
+    pub fn len(&self) -> io::Result<usize> {
+        Ok(self.coords()?.len())
+    }
+
+    pub fn is_empty(&self) -> io::Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    fn coords(&self) -> io::Result<&Arc<Coords>> {
+        if let Some(coords) = self.coords.get() {
+            return Ok(coords);
+        }
+
+        let coords = match self.format {
+            PointFileFormat::Npy => map_npy(&self.path)?,
+            PointFileFormat::Csv => parse_csv(&self.path)?,
+            PointFileFormat::RawF32 => map_raw(&self.path, Coord::F32, self.dims.unwrap())?,
+            PointFileFormat::RawF64 => map_raw(&self.path, Coord::F64, self.dims.unwrap())?,
+        };
+        if let Some(dims) = self.dims.filter(|&dims| dims != coords.dims()) {
+            return Err(invalid_data(format!(
+                "{} holds {}D points, not {}D",
+                self.path.display(),
+                coords.dims(),
+                dims
+            )));
+        }
+
+        Ok(self.coords.get_or_init(|| Arc::new(coords)))
+    }
+
+    /// Loads `qty` points from the file.
+    ///
+    /// If `qty` is the number of points in the file, they are used in place when the file
+    /// is memory-mapped with coordinates of type `A`, and converted otherwise. If it is
+    /// smaller, a random sample seeded by `seed` is taken. If it is larger, the file's
+    /// points are repeated, in a random order seeded by `seed`.
+    pub fn load<A: Scalar, const K: usize>(
+        &self,
+        qty: usize,
+        seed: u64,
+    ) -> io::Result<Points<A, K>> {
+        let coords = self.coords()?;
+        if coords.dims() != K {
+            return Err(invalid_data(format!(
+                "{} holds {}D points, not {}D",
+                self.path.display(),
+                coords.dims(),
+                K
+            )));
+        }
+
+        let len = coords.len();
+        if qty == len && coords.as_slice::<A, K>().is_some() {
+            return Ok(Points(Storage::Mapped(Arc::clone(coords))));
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let indices: Vec<usize> = if qty == len {
+            (0..len).collect()
+        } else if qty < len {
+            index::sample(&mut rng, len, qty).into_vec()
+        } else if len > 0 {
+            let mut order: Vec<usize> = (0..len).collect();
+            order.shuffle(&mut rng);
+            order.into_iter().cycle().take(qty).collect()
+        } else {
+            return Err(invalid_data(format!(
+                "{} holds no points",
+                self.path.display()
+            )));
+        };
+
+        Ok(Points::from(
+            indices
+                .into_iter()
+                .map(|idx| coords.point::<K>(idx).map(A::from_f64))
+                .collect::<Vec<_>>(),
+        ))
+    }
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn map_file(path: &Path) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+
+    // SAFETY: the mapping is only read from. As with any memory-mapped file, the
+    // file must not be modified while it is mapped.
+    unsafe { MmapOptions::new().map(&file) }
+}
+
+fn mapped(mmap: Mmap, offset: usize, coord: Coord, dims: usize, len: usize) -> io::Result<Coords> {
+    if offset + len * dims * coord.size() > mmap.len() {
+        return Err(invalid_data(format!(
+            "expected {} {}D points but the file is truncated",
+            len, dims
+        )));
+    }
+
+    Ok(Coords::Mapped {
+        mmap,
+        offset,
+        coord,
+        dims,
+        len,
+    })
+}
+
+/// Maps an NPY v1, v2 or v3 file holding a C-ordered `(N, K)` array of `<f4` or `<f8`
+fn map_npy(path: &Path) -> io::Result<Coords> {
+    let mmap = map_file(path)?;
+    if mmap.len() < 12 || &mmap[..6] != NPY_MAGIC {
+        return Err(invalid_data(format!(
+            "{} is not an NPY file",
+            path.display()
+        )));
+    }
+
+    let (header_start, header_len) = match mmap[6] {
+        1 => (10, u16::from_le_bytes([mmap[8], mmap[9]]) as usize),
+        2 | 3 => (
+            12,
+            u32::from_le_bytes(mmap[8..12].try_into().unwrap()) as usize,
+        ),
+        version => {
+            return Err(invalid_data(format!(
+                "unsupported NPY version {} in {}",
+                version,
+                path.display()
+            )))
+        }
+    };
+    let header = mmap
+        .get(header_start..header_start + header_len)
+        .map(String::from_utf8_lossy)
+        .ok_or_else(|| invalid_data(format!("truncated NPY header in {}", path.display())))?;
+
+    let coord = if header.contains("'descr': '<f8'") {
+        Coord::F64
+    } else if header.contains("'descr': '<f4'") {
+        Coord::F32
+    } else {
+        return Err(invalid_data(format!(
+            "unsupported NPY dtype in {}, expected <f4 or <f8",
+            header
+        )));
+    };
+    if !header.contains("'fortran_order': False") {
+        return Err(invalid_data(format!(
+            "unsupported NPY header {}, expected C order",
+            header
+        )));
+    }
+
+    let shape: Vec<usize> = header
+        .split("'shape': (")
+        .nth(1)
+        .and_then(|shape| shape.split_once(')'))
+        .and_then(|(shape, _)| {
+            shape
+                .split(',')
+                .map(str::trim)
+                .filter(|dim| !dim.is_empty())
+                .map(|dim| dim.parse().ok())
+                .collect()
+        })
+        .ok_or_else(|| invalid_data(format!("could not read the shape from {}", header)))?;
+    let [len, dims] = shape[..] else {
+        return Err(invalid_data(format!(
+            "expected a shape of (N, K) in {}",
+            header
+        )));
+    };
+    if dims == 0 {
+        return Err(invalid_data(format!(
+            "expected at least 1 dimension in {}",
+            header
+        )));
+    }
+
+    mapped(mmap, header_start + header_len, coord, dims, len)
+}
+
+fn map_raw(path: &Path, coord: Coord, dims: usize) -> io::Result<Coords> {
+    let mmap = map_file(path)?;
+    let point_size = dims * coord.size();
+    if mmap.len() % point_size != 0 {
+        return Err(invalid_data(format!(
+            "{} does not hold a whole number of {}D points",
+            path.display(),
+            dims
+        )));
+    }
+
+    let len = mmap.len() / point_size;
+    mapped(mmap, 0, coord, dims, len)
+}
+
+fn parse_csv(path: &Path) -> io::Result<Coords> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut values = vec![];
+    let mut dims = None;
+    let mut skipped_header = false;
+
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let point: Result<Vec<f64>, _> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|coord| !coord.is_empty())
+            .map(str::parse)
+            .collect();
+
+        match (point, dims) {
+            (Ok(point), None) => {
+                dims = Some(point.len());
+                values.extend(point);
+            }
+            (Ok(point), Some(dims)) if point.len() == dims => values.extend(point),
+            (Err(_), None) if !skipped_header => skipped_header = true,
+            _ => {
+                return Err(invalid_data(format!(
+                    "{}:{}: expected a point of {} coordinates",
+                    path.display(),
+                    line_idx + 1,
+                    dims.map_or("numeric".to_string(), |dims| dims.to_string())
+                )))
+            }
+        }
+    }
+
+    let dims = dims
+        .filter(|&dims| dims > 0)
+        .ok_or_else(|| invalid_data(format!("{} holds no points", path.display())))?;
+
+    Ok(Coords::Parsed { values, dims })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// A file in the temp directory that is removed when dropped
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, contents: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "kd-tree-comparison-{}-{}",
+                std::process::id(),
+                name
+            ));
+            fs::write(&path, contents).unwrap();
+
+            TempFile(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn le_bytes_f64(values: &[f64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn le_bytes_f32(values: &[f32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    /// An NPY v1 file, with its header padded so that the data is 64-byte aligned as numpy's is
+    fn npy(descr: &str, fortran_order: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+            descr,
+            if fortran_order { "True" } else { "False" },
+            shape
+        );
+        while (10 + header.len() + 1) % 64 != 0 {
+            header.push(' ');
+        }
+        header.push('\n');
+
+        let mut bytes = NPY_MAGIC.to_vec();
+        bytes.extend([1, 0]);
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(data);
+
+        bytes
+    }
+
+    #[test]
+    fn maps_npy_f8_in_place() {
+        let data = le_bytes_f64(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let file = TempFile::new("f8.npy", &npy("<f8", false, "(3, 2)", &data));
+        let point_file = PointFile::new(&file.0, None).unwrap();
+
+        assert_eq!(point_file.format(), PointFileFormat::Npy);
+        assert_eq!(point_file.dims().unwrap(), 2);
+        assert_eq!(point_file.len().unwrap(), 3);
+
+        let points = point_file.load::<f64, 2>(3, 0).unwrap();
+        assert!(matches!(points.0, Storage::Mapped(_)));
+        assert_eq!(&*points, &[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    }
+
+    #[test]
+    fn converts_npy_f4_to_other_scalars() {
+        let data = le_bytes_f32(&[0.5, 1.5, 2.5, 3.5]);
+        let file = TempFile::new("f4.npy", &npy("<f4", false, "(2, 2)", &data));
+        let point_file = PointFile::new(&file.0, Some(2)).unwrap();
+
+        let points = point_file.load::<f64, 2>(2, 0).unwrap();
+        assert!(matches!(points.0, Storage::Owned(_)));
+        assert_eq!(&*points, &[[0.5, 1.5], [2.5, 3.5]]);
+    }
+
+    #[test]
+    fn rejects_unsupported_npy_files() {
+        let data = le_bytes_f64(&[1.0, 2.0]);
+        for (name, contents) in [
+            ("fortran.npy", npy("<f8", true, "(1, 2)", &data)),
+            ("int.npy", npy("<i8", false, "(1, 2)", &data)),
+            ("flat.npy", npy("<f8", false, "(2,)", &data)),
+            ("zero-dims.npy", npy("<f8", false, "(2, 0)", &[])),
+            ("truncated.npy", npy("<f8", false, "(2, 2)", &data)),
+            ("not.npy", b"not an npy file".to_vec()),
+        ] {
+            let file = TempFile::new(name, &contents);
+            let error = PointFile::new(&file.0, None).unwrap().len().unwrap_err();
+
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{}", name);
+        }
+    }
+
+    #[test]
+    fn parses_csv_with_header_comments_and_mixed_separators() {
+        let contents = "# exported points\nx,y,z\n1,2,3\n4 5 6\n\n7,\t8, 9\n";
+        let file = TempFile::new("points.csv", contents.as_bytes());
+        let point_file = PointFile::new(&file.0, None).unwrap();
+
+        assert_eq!(point_file.format(), PointFileFormat::Csv);
+        assert_eq!(point_file.dims().unwrap(), 3);
+
+        let points = point_file.load::<f64, 3>(3, 0).unwrap();
+        assert_eq!(
+            &*points,
+            &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]
+        );
+    }
+
+    #[test]
+    fn rejects_csv_points_of_differing_dimensions() {
+        let file = TempFile::new("ragged.csv", b"1,2,3\n4,5\n");
+        let error = PointFile::new(&file.0, None).unwrap().len().unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_csv_without_points() {
+        let file = TempFile::new("empty.csv", b"# nothing here\nx,y\n");
+        let error = PointFile::new(&file.0, None).unwrap().len().unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn maps_raw_binary_with_given_dims() {
+        let data = le_bytes_f32(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let file = TempFile::new("points.f32", &data);
+        let point_file = PointFile::new(&file.0, Some(3)).unwrap();
+
+        assert_eq!(point_file.format(), PointFileFormat::RawF32);
+        assert_eq!(point_file.len().unwrap(), 2);
+
+        let points = point_file.load::<f32, 3>(2, 0).unwrap();
+        assert!(matches!(points.0, Storage::Mapped(_)));
+        assert_eq!(&*points, &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    }
+
+    #[test]
+    fn rejects_raw_binary_of_partial_points() {
+        let file = TempFile::new("partial.f64", &le_bytes_f64(&[1.0, 2.0, 3.0]));
+        let error = PointFile::new(&file.0, Some(2)).unwrap().len().unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn requires_positive_dims_for_raw_binary() {
+        let file = TempFile::new("no-dims.f64", &le_bytes_f64(&[1.0, 2.0]));
+
+        for dims in [None, Some(0)] {
+            let error = PointFile::new(&file.0, dims).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn rejects_unrecognised_extensions() {
+        let error = PointFile::new("points.las", None).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn rejects_loading_as_other_dims() {
+        let data = le_bytes_f64(&[1.0, 2.0, 3.0, 4.0]);
+        let file = TempFile::new("dims.f64", &data);
+        let point_file = PointFile::new(&file.0, Some(2)).unwrap();
+
+        assert!(point_file.load::<f64, 4>(1, 0).is_err());
+    }
+
+    #[test]
+    fn samples_fewer_points_and_repeats_for_more() {
+        let values: Vec<f64> = (0..20).map(f64::from).collect();
+        let file = TempFile::new("sample.f64", &le_bytes_f64(&values));
+        let point_file = PointFile::new(&file.0, Some(2)).unwrap();
+        let all: Vec<[f64; 2]> = values.chunks(2).map(|c| [c[0], c[1]]).collect();
+
+        let sample = point_file.load::<f64, 2>(4, 7).unwrap();
+        assert_eq!(sample.len(), 4);
+        assert!(sample.iter().all(|point| all.contains(point)));
+        assert_eq!(&*sample, &*point_file.load::<f64, 2>(4, 7).unwrap());
+
+        let repeated = point_file.load::<f64, 2>(25, 7).unwrap();
+        assert_eq!(repeated.len(), 25);
+        for point in &all {
+            let count = repeated.iter().filter(|p| *p == point).count();
+            assert!(count == 2 || count == 3);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Recall of the approximate indexes, measured against the results of an exact tree.
+//!
+//! Each run builds a tree over the shared dataset and queries it once with the shared query
+//! points, timing both. Those timings are much noisier than criterion's, but are taken at the
+//! same time as the recall, so that recall can be plotted against queries per second as
+//! [`ef_search`](crate::adapters::ef_search) is varied.
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+use rayon::prelude::*;
+
+use crate::adapters::{adapter_tags, Item, KdTreeAdapter, Operation};
+use crate::dataset::{dataset_points, dataset_tags, query_points};
+use crate::matrix::MatrixVisitor;
+use crate::ndjson::RecallComplete;
+use crate::scalar::Scalar;
+use crate::scenarios::QUERY_POINTS_PER_LOOP;
+use crate::utils::dims_and_type;
+
+/// Criterion-style group name used in the ids of recall results, eg `Recall@10`
+pub fn recall_group_name(k: usize) -> String {
+    format!("Recall@{}", k)
+}
+
+/// Items of the `k` nearest neighbours of each query, in order of distance
+fn neighbour_items<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    tree: &T,
+    queries: &[[A; K]],
+    k: usize,
+) -> Vec<Vec<Item>> {
+    queries
+        .par_iter()
+        .map(|query| {
+            if k == 1 {
+                vec![tree.nearest_one(query).item]
+            } else {
+                tree.nearest_n(query, k)
+                    .into_iter()
+                    .map(|neighbour| neighbour.item)
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+fn supports_k<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(k: usize) -> bool {
+    T::supports(if k == 1 {
+        Operation::NearestOne
+    } else {
+        Operation::NearestN
+    }) && T::supports_qty(k)
+}
+
+/// Fraction of the true neighbours that were found, averaged over every query
+pub fn recall(found: &[Vec<Item>], exact: &[Vec<Item>]) -> f64 {
+    let total: f64 = found
+        .iter()
+        .zip(exact)
+        .map(|(found, exact)| {
+            let exact: HashSet<_> = exact.iter().collect();
+            let hits = found.iter().filter(|item| exact.contains(item)).count();
+
+            hits as f64 / exact.len().max(1) as f64
+        })
+        .sum();
+
+    total / exact.len().max(1) as f64
+}
+
+/// The true `k` nearest neighbours of each query point, from an exact tree.
+/// `None` if the tree does not support the query.
+pub struct ExactNeighbours {
+    pub size: usize,
+    pub k: usize,
+}
+
+impl MatrixVisitor for ExactNeighbours {
+    type Output = Option<Vec<Vec<Item>>>;
+
+    fn visit<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(self) -> Self::Output {
+        if T::APPROXIMATE || !supports_k::<A, T, K>(self.k) {
+            return None;
+        }
+
+        let points = dataset_points::<A, K>(self.size);
+        let tree = T::build_from_slice(&points);
+
+        Some(neighbour_items(
+            &tree,
+            &query_points::<A, K>(QUERY_POINTS_PER_LOOP),
+            self.k,
+        ))
+    }
+}
+
+/// Measures the recall, build time and query throughput of one point of the matrix.
+/// `None` if the tree does not support the query.
+pub struct RecallRun<'a> {
+    pub size: usize,
+    pub k: usize,
+
+    /// The true neighbours, from [`ExactNeighbours`] at the same point of the matrix
+    pub exact: &'a [Vec<Item>],
+}
+
+impl MatrixVisitor for RecallRun<'_> {
+    type Output = Option<RecallComplete>;
+
+    fn visit<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(self) -> Self::Output {
+        if !supports_k::<A, T, K>(self.k) {
+            return None;
+        }
+
+        let points = dataset_points::<A, K>(self.size);
+        let queries = query_points::<A, K>(QUERY_POINTS_PER_LOOP);
+
+        let start = Instant::now();
+        let tree = T::build_from_slice(&points);
+        let build_secs = start.elapsed().as_secs_f64();
+
+        let start = Instant::now();
+        let found = neighbour_items(&tree, &queries, self.k);
+        let query_secs = start.elapsed().as_secs_f64();
+
+        Some(RecallComplete {
+            id: format!(
+                "{}/{} {}{}{}/{}",
+                recall_group_name(self.k),
+                T::NAME,
+                dims_and_type::<A, K>(),
+                adapter_tags::<A, T, K>(),
+                dataset_tags(),
+                self.size
+            ),
+            k: self.k,
+            recall: recall(&found, self.exact),
+            build_secs,
+            queries_per_sec: queries.len() as f64 / query_secs,
+        })
+    }
+}
@@ -0,0 +1,285 @@
+//! Structured benchmark results, as consumed by the webapp.
+//!
+//! Criterion ids are flattened into a single string, eg
+//! `Query Best 10/Kiddo_v5_immutable_dynamic 2D f64/100`. [`BenchmarkResult`] splits
+//! them back out into their parts, so that nothing downstream needs to parse ids.
+//!
+//! Anything else that distinguishes a run, such as a non-uniform distribution, is appended
+//! to the middle part of the id as `key=value` tags, eg `Kiddo_v2 3D f64 dist=lines`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ndjson::{BenchmarkComplete, Estimate, MemoryComplete, RecallComplete, Throughput};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidId(pub String);
+
+impl fmt::Display for InvalidId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse benchmark id '{}'", self.0)
+    }
+}
+
+impl Error for InvalidId {}
+
+/// The parts of a benchmark id
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedId {
+    /// Criterion group, eg `Query Nearest 10`
+    pub group: String,
+
+    /// Library and major version, eg `Kiddo_v5` or `scipy`
+    pub library: String,
+
+    /// Flavour of the library, eg `immutable_dynamic` for `Kiddo_v5_immutable_dynamic`
+    pub variant: Option<String>,
+
+    pub dims: usize,
+
+    /// Scalar type, eg `f64` or `FXP`
+    pub scalar: String,
+
+    /// Number of points in the tree
+    pub size: usize,
+
+    /// Any `key=value` tags that follow the scalar type, eg `dist` for the distribution
+    /// of the tree points if it was not uniform
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+}
+
+fn is_version(part: &str) -> bool {
+    part == "next"
+        || part
+            .strip_prefix('v')
+            .is_some_and(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Splits a library name as it appears in ids into the library and its variant.
+/// A version suffix such as `_v5` or `_next` is treated as part of the library.
+/// Library names of more than one word are joined with `-`, eg `brute-force`.
+fn split_library(name: &str) -> (String, Option<String>) {
+    let mut parts = name.splitn(3, '_');
+    let mut library = parts.next().unwrap_or_default().to_string();
+    let mut variant = vec![];
+
+    if let Some(second) = parts.next() {
+        if is_version(second) {
+            library = format!("{}_{}", library, second);
+        } else {
+            variant.push(second);
+        }
+    }
+    variant.extend(parts);
+
+    let variant = if variant.is_empty() {
+        None
+    } else {
+        Some(variant.join("_"))
+    };
+
+    (library, variant)
+}
+
+impl ParsedId {
+    pub fn parse(id: &str) -> Result<Self, InvalidId> {
+        let invalid = || InvalidId(id.to_string());
+
+        let (group, rest) = id.split_once('/').ok_or_else(invalid)?;
+        let (subtype, size) = rest.rsplit_once('/').ok_or_else(invalid)?;
+        let size = size.parse().map_err(|_| invalid())?;
+
+        let subtype: Vec<_> = subtype.split(' ').collect();
+        let [name, dims, scalar, ref tags @ ..] = subtype[..] else {
+            return Err(invalid());
+        };
+        let tags = tags
+            .iter()
+            .map(|tag| {
+                tag.split_once('=')
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?;
+        let dims = dims
+            .strip_suffix('D')
+            .and_then(|dims| dims.parse().ok())
+            .ok_or_else(invalid)?;
+        let (library, variant) = split_library(name);
+
+        Ok(ParsedId {
+            group: group.to_string(),
+            library,
+            variant,
+            dims,
+            scalar: scalar.to_string(),
+            size,
+            tags,
+        })
+    }
+}
+
+/// A single benchmark's results, with its id broken down into its parts
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub id: String,
+
+    #[serde(flatten)]
+    pub parsed: ParsedId,
+
+    pub throughput: Option<Throughput>,
+    pub typical: Estimate,
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub median_abs_dev: Estimate,
+    pub slope: Option<Estimate>,
+}
+
+impl TryFrom<BenchmarkComplete> for BenchmarkResult {
+    type Error = InvalidId;
+
+    /// Drops the raw samples and the machine-local `report_directory`
+    fn try_from(benchmark: BenchmarkComplete) -> Result<Self, Self::Error> {
+        Ok(BenchmarkResult {
+            parsed: ParsedId::parse(&benchmark.id)?,
+            id: benchmark.id,
+            throughput: benchmark.throughput.into_iter().next(),
+            typical: benchmark.typical,
+            mean: benchmark.mean,
+            median: benchmark.median,
+            median_abs_dev: benchmark.median_abs_dev,
+            slope: benchmark.slope,
+        })
+    }
+}
+
+/// A single tree's memory footprint, with its id broken down into its parts
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MemoryResult {
+    pub id: String,
+
+    #[serde(flatten)]
+    pub parsed: ParsedId,
+
+    pub retained_bytes: usize,
+    pub peak_bytes: usize,
+}
+
+impl TryFrom<MemoryComplete> for MemoryResult {
+    type Error = InvalidId;
+
+    fn try_from(memory: MemoryComplete) -> Result<Self, Self::Error> {
+        Ok(MemoryResult {
+            parsed: ParsedId::parse(&memory.id)?,
+            id: memory.id,
+            retained_bytes: memory.retained_bytes,
+            peak_bytes: memory.peak_bytes,
+        })
+    }
+}
+
+/// A single tree's recall and timings, with its id broken down into its parts
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecallResult {
+    pub id: String,
+
+    #[serde(flatten)]
+    pub parsed: ParsedId,
+
+    pub k: usize,
+    pub recall: f64,
+    pub build_secs: f64,
+    pub queries_per_sec: f64,
+}
+
+impl TryFrom<RecallComplete> for RecallResult {
+    type Error = InvalidId;
+
+    fn try_from(recall: RecallComplete) -> Result<Self, Self::Error> {
+        Ok(RecallResult {
+            parsed: ParsedId::parse(&recall.id)?,
+            id: recall.id,
+            k: recall.k,
+            recall: recall.recall,
+            build_secs: recall.build_secs,
+            queries_per_sec: recall.queries_per_sec,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_versioned_library_and_variant() {
+        let parsed =
+            ParsedId::parse("Query Best 10/Kiddo_v5_immutable_dynamic 2D f64/100").unwrap();
+
+        assert_eq!(parsed.group, "Query Best 10");
+        assert_eq!(parsed.library, "Kiddo_v5");
+        assert_eq!(parsed.variant.as_deref(), Some("immutable_dynamic"));
+        assert_eq!(parsed.dims, 2);
+        assert_eq!(parsed.scalar, "f64");
+        assert_eq!(parsed.size, 100);
+        assert!(parsed.tags.is_empty());
+    }
+
+    #[test]
+    fn parses_next_as_a_version() {
+        let parsed = ParsedId::parse("Query Nearest 1/Kiddo_next_immutable 3D f32/1000").unwrap();
+
+        assert_eq!(parsed.library, "Kiddo_next");
+        assert_eq!(parsed.variant.as_deref(), Some("immutable"));
+    }
+
+    #[test]
+    fn parses_library_without_variant() {
+        let parsed = ParsedId::parse("Query Nearest 10/scipy 4D f64/1000000").unwrap();
+
+        assert_eq!(parsed.library, "scipy");
+        assert_eq!(parsed.variant, None);
+    }
+
+    #[test]
+    fn parses_hyphenated_library_names_whole() {
+        for name in ["brute-force", "petal-balltree", "kd-tree", "hnsw-rs"] {
+            let parsed = ParsedId::parse(&format!("Query Nearest 10/{} 3D f64/100", name)).unwrap();
+
+            assert_eq!(parsed.library, name);
+            assert_eq!(parsed.variant, None);
+        }
+    }
+
+    #[test]
+    fn parses_tags() {
+        let parsed =
+            ParsedId::parse("Query Nearest n/Kiddo_v2 3D f64 leaf=64 k=50 dist=lines/10000")
+                .unwrap();
+
+        let expected: BTreeMap<_, _> = [("leaf", "64"), ("k", "50"), ("dist", "lines")]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        assert_eq!(parsed.tags, expected);
+        assert_eq!(parsed.size, 10000);
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        for id in [
+            "no slashes",
+            "Group/Kiddo_v2 3D f64",
+            "Group/Kiddo_v2 3D f64/many",
+            "Group/Kiddo_v2 3 f64/100",
+            "Group/Kiddo_v2 3D/100",
+            "Group/Kiddo_v2 3D f64 untagged/100",
+        ] {
+            assert_eq!(ParsedId::parse(id), Err(InvalidId(id.to_string())));
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt::Debug;
+
+use fixed::types::extra::U16;
+use fixed::FixedU16;
+
+pub use fixed::types::{I16F16, I1F31, I8F24, U0F32, U0F8, U16F16, U4F12, U4F4, U8F24, U8F8};
+
+/// Fixed point type used by the fixed-point kiddo scenarios
+pub type FXP = FixedU16<U16>;
+
+/// A coordinate type that the trees under test can be built over.
+///
+/// Points are always generated as `f64` and converted, so that every scalar
+/// type is fed from the same underlying values.
+pub trait Scalar: Copy + Debug + PartialOrd + Send + Sync + 'static {
+    /// Name of the type as it appears in benchmark ids, eg `f64`
+    const NAME: &'static str;
+
+    /// Relative tolerance when comparing distances calculated by different libraries
+    const EPSILON: f64;
+
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f64(self) -> f64;
+}
+
+impl Scalar for f32 {
+    const NAME: &'static str = "f32";
+    const EPSILON: f64 = 1e-5;
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for f64 {
+    const NAME: &'static str = "f64";
+    const EPSILON: f64 = 1e-12;
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// Implements [`Scalar`] for fixed point types, saturating values that are out of range
+macro_rules! impl_fixed_scalar {
+    ($($t:ty: $name:literal, $epsilon:literal;)+) => {
+        $(
+            impl Scalar for $t {
+                const NAME: &'static str = $name;
+                const EPSILON: f64 = $epsilon;
+
+                fn from_f64(value: f64) -> Self {
+                    <$t>::saturating_from_num(value)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self.to_num()
+                }
+            }
+        )+
+    };
+}
+
+// FXP keeps its own name, rather than U0F16, so that its ids match earlier results
+impl_fixed_scalar! {
+    FXP: "FXP", 1e-4;
+    U0F8: "U0F8", 1e-1;
+    U4F4: "U4F4", 5e-1;
+    U8F8: "U8F8", 1e-1;
+    U4F12: "U4F12", 1e-2;
+    U16F16: "U16F16", 1e-4;
+    U8F24: "U8F24", 1e-6;
+    U0F32: "U0F32", 1e-7;
+    I16F16: "I16F16", 1e-4;
+    I8F24: "I8F24", 1e-6;
+    I1F31: "I1F31", 1e-7;
+}
@@ -0,0 +1,408 @@
+//! Benchmark scenarios, written once against [`KdTreeAdapter`] and shared by every library.
+//!
+//! Trees are built from, and queried with, the seeded datasets or point files in
+//! [`crate::dataset`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use criterion::measurement::WallTime;
+use criterion::{black_box, BatchSize, BenchmarkGroup, BenchmarkId};
+use rayon::prelude::*;
+
+use crate::adapters::{adapter_tags, KdTreeAdapter};
+use crate::dataset::{dataset_points, dataset_tags, query_points};
+use crate::point_file::Points;
+use crate::scalar::Scalar;
+use crate::utils::dims_and_type;
+use crate::workload::{workload_ops, OperationMix, Removal, WorkloadOp};
+
+pub const QUERY_POINTS_PER_LOOP: usize = 1_000;
+pub const RADIUS_QUERY_POINTS_PER_LOOP: usize = 100;
+pub const WORKLOAD_OPS_PER_LOOP: usize = 1_000;
+pub const WORKLOAD_K: usize = 10;
+pub const RADIUS: f64 = 0.01;
+pub const BEST_N_RADIUS: f64 = 0.05;
+
+/// Result counts for the k sweep of nearest_n and best_n_within
+pub const K_SWEEP: [usize; 9] = [1, 2, 5, 10, 20, 50, 100, 500, 1000];
+
+/// Squared radii for the radius sweep of the radius-based queries
+pub const RADIUS_SWEEP: [f64; 6] = [0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05];
+
+/// Leaf sizes for the leaf size sweep, of every library that exposes one
+pub const LEAF_SIZE_SWEEP: [usize; 6] = [8, 16, 32, 64, 128, 256];
+
+/// Tree sizes used by the k, radius and leaf size sweeps, which multiply the matrix by each
+/// parameter value. Small enough for the brute force baseline, so every library runs at every size.
+pub const SIZES_SWEEP: [usize; 2] = [10_000, 100_000];
+
+/// Tree sizes used by the full matrix
+pub const SIZES: [usize; 6] = [100, 1_000, 10_000, 100_000, 1_000_000, 10_000_000];
+
+/// Sizes used by the brute force baseline, which is too slow to scan 10M points per query
+pub const SIZES_BRUTE_FORCE: [usize; 4] = [100, 1_000, 10_000, 100_000];
+
+/// Tree sizes used by the approximate indexes, whose graph construction is too slow for 10M points
+pub const SIZES_ANN: [usize; 4] = [1_000, 10_000, 100_000, 1_000_000];
+
+/// Tree sizes used by the fixed point matrix, which is too wide to run at every size
+pub const SIZES_FIXED: [usize; 4] = [1_000, 10_000, 100_000, 1_000_000];
+
+/// Tree sizes used by kiddo's immutable trees when built over `f32`
+pub const SIZES_IMMUTABLE_F32: [usize; 4] = [100, 1_000, 10_000, 100_000];
+
+/// Above 4 dimensions, trees are capped at this many coordinates, ie points times dimensions.
+/// Queries approach a linear scan as dimensions increase, so the largest trees would take
+/// far too long to benchmark.
+pub const HIGH_DIMS_MAX_COORDINATES: usize = 8_000_000;
+
+/// Whether a tree of `size` points is small enough to benchmark in `dims` dimensions.
+/// Applied by [`bench_matrix!`], so high dimensional runs are capped automatically.
+pub fn size_within_dims_cap(dims: usize, size: usize) -> bool {
+    dims <= 4 || size * dims <= HIGH_DIMS_MAX_COORDINATES
+}
+
+/// Runs a scenario for an adapter across every combination of `(scalar type, dimensions)`
+/// and tree size. Any extra arguments in the trailing brackets are passed on to the scenario.
+/// Combinations that the run's point files can not supply, or that are too large for their
+/// dimensions, are skipped.
+///
+/// ```ignore
+/// bench_matrix!(group, bench_nearest_n, KiddoV2, [(f32, 2), (f64, 3)], SIZES, (10));
+/// ```
+#[macro_export]
+macro_rules! bench_matrix {
+    ($group:ident, $scenario:ident, $adapter:ident, [$(($a:ty, $k:tt)),+ $(,)?], $sizes:expr, $args:tt) => {
+        $(
+            for &size in $sizes.iter() {
+                $crate::bench_matrix!(@call $group, $scenario, $adapter, $a, $k, size, $args);
+            }
+        )+
+    };
+    ($group:ident, $scenario:ident, $adapter:ident, [$(($a:ty, $k:tt)),+ $(,)?], $sizes:expr) => {
+        $crate::bench_matrix!($group, $scenario, $adapter, [$(($a, $k)),+], $sizes, ());
+    };
+    (@call $group:ident, $scenario:ident, $adapter:ident, $a:ty, $k:tt, $size:ident, ($($arg:expr),*)) => {
+        if $crate::dataset::dataset_available($k, $size)
+            && $crate::scenarios::size_within_dims_cap($k, $size)
+        {
+            $scenario::<$a, $adapter<$a, $k>, $k>(&mut $group, $size $(, $arg)*);
+        }
+    };
+}
+
+/// Tags for the query parameters of the scenarios currently running, when swept
+static PARAMETER_TAGS: RwLock<String> = RwLock::new(String::new());
+
+/// Runs `f` with the ids of every benchmark that it runs tagged with the value of a swept
+/// query parameter, eg ` k=50` or ` radius=0.001`. Calls can be nested to tag several.
+pub fn with_parameter_tag<R>(key: &str, value: impl fmt::Display, f: impl FnOnce() -> R) -> R {
+    let previous = PARAMETER_TAGS.read().unwrap().clone();
+    *PARAMETER_TAGS.write().unwrap() = format!("{} {}={}", previous, key, value);
+    let result = f();
+    *PARAMETER_TAGS.write().unwrap() = previous;
+
+    result
+}
+
+/// The tags added by [`with_parameter_tag`], for benches that build their own ids, such as
+/// the Python ones
+pub fn parameter_tags() -> String {
+    PARAMETER_TAGS.read().unwrap().clone()
+}
+
+fn benchmark_id<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(size: usize) -> BenchmarkId {
+    tagged_benchmark_id::<A, T, K>(size, "")
+}
+
+/// Benchmark id with extra ` key=value` tags for the scenario's own parameters
+fn tagged_benchmark_id<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    size: usize,
+    tags: &str,
+) -> BenchmarkId {
+    BenchmarkId::new(
+        format!(
+            "{} {}{}{}{}{}",
+            T::NAME,
+            dims_and_type::<A, K>(),
+            adapter_tags::<A, T, K>(),
+            PARAMETER_TAGS.read().unwrap(),
+            tags,
+            dataset_tags()
+        ),
+        size,
+    )
+}
+
+fn build_populated_tree_and_query_points<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    initial_size: usize,
+    query_point_qty: usize,
+) -> (T, Points<A, K>) {
+    let initial_points = dataset_points::<A, K>(initial_size);
+    let tree = T::build_from_slice(&initial_points);
+
+    (tree, query_points::<A, K>(query_point_qty))
+}
+
+/// Builds a tree from every point at once. Only construction is timed: converting the points
+/// into the library's own input, and dropping the tree, are left out.
+pub fn bench_add_to_empty<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    qty_to_add: usize,
+) {
+    let points = dataset_points::<A, K>(qty_to_add);
+
+    group.bench_with_input(benchmark_id::<A, T, K>(qty_to_add), &points, |b, points| {
+        b.iter_batched(
+            || T::prepare(points),
+            |input| T::build(black_box(input)),
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+/// Removes a percentage of a populated tree's points, in random or spatially clustered order.
+/// Every iteration starts from a freshly built tree, and only the removals are timed.
+/// The group's throughput should be set to the number of points removed, [`Removal::qty`].
+pub fn bench_remove<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    removal: Removal,
+) {
+    let points = dataset_points::<A, K>(initial_size);
+    let to_remove = removal.sequence(&points);
+
+    group.bench_function(
+        tagged_benchmark_id::<A, T, K>(initial_size, &removal.tags()),
+        |b| {
+            b.iter_batched(
+                || T::build_from_slice(&points),
+                |mut tree| {
+                    for (item, point) in &to_remove {
+                        black_box(tree.remove(black_box(point), *item));
+                    }
+
+                    tree
+                },
+                BatchSize::PerIteration,
+            );
+        },
+    );
+}
+
+/// nearest_n queries against a tree that has had a percentage of its points removed, to show
+/// any slowdown from emptied or unbalanced buckets
+pub fn bench_nearest_n_after_remove<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    removal: Removal,
+    qty: usize,
+) {
+    let (mut tree, query_points) =
+        build_populated_tree_and_query_points::<A, T, K>(initial_size, QUERY_POINTS_PER_LOOP);
+    for (item, point) in removal.sequence(&dataset_points::<A, K>(initial_size)) {
+        tree.remove(&point, item);
+    }
+
+    group.bench_function(
+        tagged_benchmark_id::<A, T, K>(initial_size, &removal.tags()),
+        |b| {
+            b.iter(|| {
+                query_points.par_iter().for_each(|point| {
+                    black_box(tree.nearest_n(point, qty));
+                });
+            });
+        },
+    );
+}
+
+pub fn bench_nearest_one<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+) {
+    let (tree, query_points) =
+        build_populated_tree_and_query_points::<A, T, K>(initial_size, QUERY_POINTS_PER_LOOP);
+
+    group.bench_function(benchmark_id::<A, T, K>(initial_size), |b| {
+        b.iter(|| {
+            query_points.par_iter().for_each(|point| {
+                black_box(tree.nearest_one(point));
+            });
+        });
+    });
+}
+
+/// Skipped for adapters that can not return `qty` results, see [`KdTreeAdapter::max_results`]
+pub fn bench_nearest_n<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    qty: usize,
+) {
+    if !T::supports_qty(qty) {
+        return;
+    }
+
+    let (tree, query_points) =
+        build_populated_tree_and_query_points::<A, T, K>(initial_size, QUERY_POINTS_PER_LOOP);
+
+    group.bench_function(benchmark_id::<A, T, K>(initial_size), |b| {
+        b.iter(|| {
+            query_points.par_iter().for_each(|point| {
+                black_box(tree.nearest_n(point, qty));
+            });
+        });
+    });
+}
+
+pub fn bench_within<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    radius: f64,
+) {
+    let (tree, query_points) = build_populated_tree_and_query_points::<A, T, K>(
+        initial_size,
+        RADIUS_QUERY_POINTS_PER_LOOP,
+    );
+    let radius = A::from_f64(radius);
+
+    group.bench_function(benchmark_id::<A, T, K>(initial_size), |b| {
+        b.iter(|| {
+            query_points.par_iter().for_each(|point| {
+                black_box(tree.within(point, radius));
+            });
+        });
+    });
+}
+
+pub fn bench_within_unsorted<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    radius: f64,
+) {
+    let (tree, query_points) = build_populated_tree_and_query_points::<A, T, K>(
+        initial_size,
+        RADIUS_QUERY_POINTS_PER_LOOP,
+    );
+    let radius = A::from_f64(radius);
+
+    group.bench_function(benchmark_id::<A, T, K>(initial_size), |b| {
+        b.iter(|| {
+            query_points.par_iter().for_each(|point| {
+                black_box(tree.within_unsorted(point, radius));
+            });
+        });
+    });
+}
+
+/// Result cap for the nearest-n-within scenarios, scaled with tree size so that
+/// the cap is actually reached at [`RADIUS`] for larger trees
+pub fn nearest_n_within_max_results(initial_size: usize) -> usize {
+    let max_results_map = HashMap::from([
+        (100usize, 3usize),
+        (1_000, 10),
+        (10_000, 100),
+        (100_000, 100),
+        (1_000_000, 100),
+        (10_000_000, 1000),
+    ]);
+
+    *max_results_map.get(&initial_size).unwrap_or(&100)
+}
+
+pub fn bench_nearest_n_within<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    radius: f64,
+    sorted: bool,
+) {
+    let (tree, query_points) = build_populated_tree_and_query_points::<A, T, K>(
+        initial_size,
+        RADIUS_QUERY_POINTS_PER_LOOP,
+    );
+    let radius = A::from_f64(radius);
+    let max_results = nearest_n_within_max_results(initial_size);
+
+    group.bench_function(benchmark_id::<A, T, K>(initial_size), |b| {
+        b.iter(|| {
+            query_points.par_iter().for_each(|point| {
+                black_box(tree.nearest_n_within(point, radius, max_results, sorted));
+            });
+        });
+    });
+}
+
+pub fn bench_best_n_within<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    radius: f64,
+    qty: usize,
+) {
+    let (tree, query_points) =
+        build_populated_tree_and_query_points::<A, T, K>(initial_size, QUERY_POINTS_PER_LOOP);
+    let radius = A::from_f64(radius);
+
+    group.bench_function(benchmark_id::<A, T, K>(initial_size), |b| {
+        b.iter(|| {
+            query_points.par_iter().for_each(|point| {
+                black_box(tree.best_n_within(point, radius, qty));
+            });
+        });
+    });
+}
+
+/// Replays [`WORKLOAD_OPS_PER_LOOP`] adds, nearest_n queries and removes in the proportions of
+/// `mix` against a populated tree, one at a time. Ids are tagged ` mix=<add>:<nearest_n>:<remove>`.
+///
+/// Only the replay is timed. Its operations are undone between iterations, so each iteration
+/// starts from the same set of points, though not necessarily the same tree structure.
+pub fn bench_mixed_workload<A: Scalar, T: KdTreeAdapter<A, K>, const K: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    initial_size: usize,
+    mix: OperationMix,
+) {
+    let points = dataset_points::<A, K>(initial_size);
+    let mut tree = T::build_from_slice(&points);
+
+    // Added points come from the query dataset, so that they are drawn from the same
+    // distribution as the tree without duplicating any of its points
+    let extra_points = query_points::<A, K>(2 * WORKLOAD_OPS_PER_LOOP);
+    let (new_points, queries) = extra_points.split_at(WORKLOAD_OPS_PER_LOOP);
+    let ops = workload_ops(mix, &points, new_points, queries, WORKLOAD_OPS_PER_LOOP);
+
+    let id = tagged_benchmark_id::<A, T, K>(initial_size, &format!(" mix={}", mix));
+    group.bench_function(id, |b| {
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                let start = Instant::now();
+                for op in &ops {
+                    match op {
+                        WorkloadOp::Add(item, point) => tree.add(black_box(point), *item),
+                        WorkloadOp::NearestN(query) => {
+                            black_box(tree.nearest_n(black_box(query), WORKLOAD_K));
+                        }
+                        WorkloadOp::Remove(item, point) => {
+                            black_box(tree.remove(black_box(point), *item));
+                        }
+                    }
+                }
+                elapsed += start.elapsed();
+
+                for op in ops.iter().rev() {
+                    match op {
+                        WorkloadOp::Add(item, point) => {
+                            tree.remove(point, *item);
+                        }
+                        WorkloadOp::NearestN(_) => {}
+                        WorkloadOp::Remove(item, point) => tree.add(point, *item),
+                    }
+                }
+            }
+
+            elapsed
+        });
+    });
+}
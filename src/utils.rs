@@ -0,0 +1,6 @@
+use crate::scalar::Scalar;
+
+/// Name used in benchmark ids for the given dimensionality and scalar type, eg `3D f64`
+pub fn dims_and_type<A: Scalar, const K: usize>() -> String {
+    format!("{}D {}", K, A::NAME)
+}
@@ -0,0 +1,235 @@
+//! Competing builds of the suite, each with its own set of cargo features, eg kiddo with and
+//! without its `modified_van_emde_boas` layout.
+//!
+//! Each variant's results are tagged with its name, as a `variant=<name>` tag at the end of
+//! the middle part of every id, so that the runs can be merged into one file without their
+//! ids colliding. Codegen profiles are tagged the same way, as `codegen=<profile>`.
+//! [`split_by_tag`] takes them apart again for diffing.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ndjson::{BenchmarkComplete, GroupComplete, MemoryComplete, Message, RecallComplete};
+
+/// Key of the tag that records which variant a result came from
+pub const VARIANT_TAG: &str = "variant";
+
+/// A named set of cargo features to build the suite with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    pub features: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidVariant(pub String);
+
+impl fmt::Display for InvalidVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid variant '{}', expected <name>=<feature>,<feature>,...",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidVariant {}
+
+impl FromStr for Variant {
+    type Err = InvalidVariant;
+
+    /// Parses `<name>=<features>`, eg `mveb=kiddo_next/simd,kiddo_next/modified_van_emde_boas`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidVariant(s.to_string());
+
+        let (name, features) = s.split_once('=').ok_or_else(invalid)?;
+        let name_is_valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !name_is_valid {
+            return Err(invalid());
+        }
+
+        Ok(Variant {
+            name: name.to_string(),
+            features: features
+                .split(',')
+                .map(str::trim)
+                .filter(|feature| !feature.is_empty())
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+}
+
+/// Adds a `key=value` tag to a benchmark id, eg `Query Nearest 1/Kiddo_next_immutable 3D f64/1000`
+/// becomes `Query Nearest 1/Kiddo_next_immutable 3D f64 variant=mveb/1000`
+pub fn tag_id(id: &str, key: &str, value: &str) -> String {
+    match id.rsplit_once('/') {
+        Some((rest, size)) => format!("{} {}={}/{}", rest, key, value, size),
+        None => format!("{} {}={}", id, key, value),
+    }
+}
+
+/// Removes the tag with the given key from a benchmark id, returning the untagged id and
+/// the tag's value
+pub fn split_tag(id: &str, key: &str) -> (String, Option<String>) {
+    let prefix = format!(" {}=", key);
+    let Some(start) = id.find(&prefix) else {
+        return (id.to_string(), None);
+    };
+
+    let value_start = start + prefix.len();
+    let value_end = id[value_start..]
+        .find([' ', '/'])
+        .map_or(id.len(), |end| value_start + end);
+
+    (
+        format!("{}{}", &id[..start], &id[value_end..]),
+        Some(id[value_start..value_end].to_string()),
+    )
+}
+
+/// Adds `key=value` tags to every id in a message
+pub fn tag_message(message: Message, tags: &[(&str, &str)]) -> Message {
+    let tag = |id: &str| {
+        tags.iter()
+            .fold(id.to_string(), |id, (key, value)| tag_id(&id, key, value))
+    };
+
+    match message {
+        Message::BenchmarkComplete(benchmark) => {
+            Message::BenchmarkComplete(Box::new(BenchmarkComplete {
+                id: tag(&benchmark.id),
+                ..*benchmark
+            }))
+        }
+        Message::GroupComplete(group) => Message::GroupComplete(GroupComplete {
+            benchmarks: group.benchmarks.iter().map(|id| tag(id)).collect(),
+            ..group
+        }),
+        Message::MemoryComplete(memory) => Message::MemoryComplete(MemoryComplete {
+            id: tag(&memory.id),
+            ..memory
+        }),
+        Message::RecallComplete(recall) => Message::RecallComplete(RecallComplete {
+            id: tag(&recall.id),
+            ..recall
+        }),
+    }
+}
+
+/// Groups benchmarks by the value of one of their tags, eg [`VARIANT_TAG`], with that tag
+/// removed from their ids so that the same benchmark matches across groups. Benchmarks
+/// without the tag are grouped under `None`.
+pub fn split_by_tag(
+    benchmarks: Vec<BenchmarkComplete>,
+    key: &str,
+) -> BTreeMap<Option<String>, Vec<BenchmarkComplete>> {
+    let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for benchmark in benchmarks {
+        let (id, value) = split_tag(&benchmark.id, key);
+        groups
+            .entry(value)
+            .or_default()
+            .push(BenchmarkComplete { id, ..benchmark });
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_variants() {
+        let variant: Variant = "mveb=kiddo_next/simd, kiddo_next/modified_van_emde_boas,"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            variant,
+            Variant {
+                name: "mveb".to_string(),
+                features: vec![
+                    "kiddo_next/simd".to_string(),
+                    "kiddo_next/modified_van_emde_boas".to_string()
+                ],
+            }
+        );
+
+        let variant: Variant = "base-line=".parse().unwrap();
+        assert_eq!(variant.name, "base-line");
+        assert!(variant.features.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_variants() {
+        for s in [
+            "mveb",
+            "=kiddo_next/simd",
+            "m veb=kiddo_next/simd",
+            "a/b=simd",
+        ] {
+            assert_eq!(s.parse::<Variant>(), Err(InvalidVariant(s.to_string())));
+        }
+    }
+
+    #[test]
+    fn tags_before_the_size() {
+        assert_eq!(
+            tag_id("Query Nearest 1/Kiddo_v5 3D f64/1000", "variant", "mveb"),
+            "Query Nearest 1/Kiddo_v5 3D f64 variant=mveb/1000"
+        );
+        assert_eq!(
+            tag_id("Kiddo_v5 3D f64", "variant", "mveb"),
+            "Kiddo_v5 3D f64 variant=mveb"
+        );
+    }
+
+    #[test]
+    fn splits_tags_wherever_they_are() {
+        assert_eq!(
+            split_tag("Add/Kiddo_v5 3D f64 variant=mveb/1000", "variant"),
+            (
+                "Add/Kiddo_v5 3D f64/1000".to_string(),
+                Some("mveb".to_string())
+            )
+        );
+        assert_eq!(
+            split_tag(
+                "Add/Kiddo_v5 3D f64 variant=mveb codegen=lto/1000",
+                "variant"
+            ),
+            (
+                "Add/Kiddo_v5 3D f64 codegen=lto/1000".to_string(),
+                Some("mveb".to_string())
+            )
+        );
+        assert_eq!(
+            split_tag("Kiddo_v5 3D f64 variant=mveb", "variant"),
+            ("Kiddo_v5 3D f64".to_string(), Some("mveb".to_string()))
+        );
+        assert_eq!(
+            split_tag("Add/Kiddo_v5 3D f64 codegen=lto/1000", "variant"),
+            ("Add/Kiddo_v5 3D f64 codegen=lto/1000".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn split_tag_undoes_tag_id() {
+        let id = "Query Nearest 10/Kiddo_next_immutable 4D f32/100000";
+        let tagged = tag_id(&tag_id(id, "variant", "mveb"), "codegen", "native");
+
+        let (id_without_variant, variant) = split_tag(&tagged, "variant");
+        assert_eq!(variant.as_deref(), Some("mveb"));
+
+        let (untagged, codegen) = split_tag(&id_without_variant, "codegen");
+        assert_eq!(codegen.as_deref(), Some("native"));
+        assert_eq!(untagged, id);
+    }
+}
@@ -0,0 +1,421 @@
+//! Workloads that mutate a populated tree: the removals made by the remove scenarios, and
+//! the operation mixes for the mixed workload scenario, which replays a seeded sequence of
+//! adds, nearest_n queries and removes, as a tree that is continuously mutated while being
+//! queried would see.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::{Rng, SeedableRng};
+
+use crate::adapters::Item;
+use crate::dataset::DATASET_SEED;
+use crate::scalar::Scalar;
+
+/// Percentages of a tree's points that the remove scenarios remove
+pub const REMOVAL_PERCENTAGES: [usize; 3] = [1, 10, 50];
+
+pub const REMOVAL_ORDERS: [RemovalOrder; 2] = [RemovalOrder::Random, RemovalOrder::Clustered];
+
+/// Order that points are removed from a tree in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RemovalOrder {
+    /// A random sample of the tree's points
+    Random,
+
+    /// The points nearest to one of the tree's points, nearest first,
+    /// so that one region of the tree is emptied
+    Clustered,
+}
+
+impl RemovalOrder {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RemovalOrder::Random => "random",
+            RemovalOrder::Clustered => "clustered",
+        }
+    }
+}
+
+/// How many of a tree's points the remove scenarios remove, and in what order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Removal {
+    pub percent: usize,
+    pub order: RemovalOrder,
+}
+
+impl Removal {
+    /// Every combination of [`REMOVAL_PERCENTAGES`] and [`REMOVAL_ORDERS`]
+    pub fn all() -> impl Iterator<Item = Removal> {
+        REMOVAL_PERCENTAGES.into_iter().flat_map(|percent| {
+            REMOVAL_ORDERS
+                .into_iter()
+                .map(move |order| Removal { percent, order })
+        })
+    }
+
+    /// Suffix for benchmark ids, eg ` removed=10% order=clustered`
+    pub fn tags(&self) -> String {
+        format!(" removed={}% order={}", self.percent, self.order.name())
+    }
+
+    /// Number of points removed from a tree of `size` points. At least one point is always
+    /// removed.
+    pub fn qty(&self, size: usize) -> usize {
+        (size * self.percent / 100).clamp(1, size)
+    }
+
+    /// The items and points to remove from a tree built from `points`, in the order to
+    /// remove them
+    pub fn sequence<A: Scalar, const K: usize>(&self, points: &[[A; K]]) -> Vec<(Item, [A; K])> {
+        let qty = self.qty(points.len());
+
+        let indices: Vec<usize> = match self.order {
+            // Sampled, rather than taken from the front, as point files can be stored in
+            // scan order. Seeded, so that every library removes the same points.
+            RemovalOrder::Random => {
+                let mut rng = StdRng::seed_from_u64(DATASET_SEED);
+                index::sample(&mut rng, points.len(), qty).into_vec()
+            }
+
+            RemovalOrder::Clustered => {
+                let centre = points[0];
+                let distance = |idx: &usize| -> f64 {
+                    points[*idx]
+                        .iter()
+                        .zip(&centre)
+                        .map(|(a, b)| (a.to_f64() - b.to_f64()).powi(2))
+                        .sum()
+                };
+
+                let mut indices: Vec<usize> = (0..points.len()).collect();
+                indices.select_nth_unstable_by(qty - 1, |a, b| {
+                    distance(a).partial_cmp(&distance(b)).unwrap()
+                });
+                indices.truncate(qty);
+                indices.sort_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap());
+
+                indices
+            }
+        };
+
+        indices
+            .into_iter()
+            .map(|idx| (idx as Item, points[idx]))
+            .collect()
+    }
+}
+
+/// Relative proportions of adds, nearest_n queries and removes in a mixed workload.
+///
+/// Written as `<add>:<nearest_n>:<remove>` on the command line and in benchmark ids,
+/// eg `50:40:10` for 50% adds, 40% nearest_n queries and 10% removes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OperationMix {
+    pub add: u32,
+    pub nearest_n: u32,
+    pub remove: u32,
+}
+
+/// The mixes run by default: add-heavy growth, a read-heavy tree that changes slowly,
+/// and churn that keeps the tree at roughly the same size
+pub const WORKLOAD_MIXES: [OperationMix; 3] = [
+    OperationMix {
+        add: 50,
+        nearest_n: 40,
+        remove: 10,
+    },
+    OperationMix {
+        add: 10,
+        nearest_n: 80,
+        remove: 10,
+    },
+    OperationMix {
+        add: 30,
+        nearest_n: 40,
+        remove: 30,
+    },
+];
+
+impl OperationMix {
+    fn total(&self) -> u32 {
+        self.add + self.nearest_n + self.remove
+    }
+}
+
+impl fmt::Display for OperationMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.add, self.nearest_n, self.remove)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidMix(pub String);
+
+impl fmt::Display for InvalidMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid operation mix '{}', expected <add>:<nearest_n>:<remove>, eg 50:40:10",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidMix {}
+
+impl FromStr for OperationMix {
+    type Err = InvalidMix;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidMix(s.to_string());
+
+        let parts = s
+            .split(':')
+            .map(|part| part.trim().parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        let [add, nearest_n, remove] = parts[..] else {
+            return Err(invalid());
+        };
+
+        let mix = OperationMix {
+            add,
+            nearest_n,
+            remove,
+        };
+        if mix.total() == 0 {
+            return Err(invalid());
+        }
+
+        Ok(mix)
+    }
+}
+
+/// A single step of a mixed workload
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkloadOp<A, const K: usize> {
+    Add(Item, [A; K]),
+    NearestN([A; K]),
+    Remove(Item, [A; K]),
+}
+
+/// Generates `qty` operations in the proportions of `mix`, against a tree built from
+/// `tree_points` with each point's index as its item.
+///
+/// Added points are taken from `new_points` in turn, with items following on from the
+/// tree's. Removes pick a random point that is in the tree at that step, including ones added
+/// earlier in the sequence. A remove that would find the tree empty is a query instead.
+pub fn workload_ops<A: Copy, const K: usize>(
+    mix: OperationMix,
+    tree_points: &[[A; K]],
+    new_points: &[[A; K]],
+    queries: &[[A; K]],
+    qty: usize,
+) -> Vec<WorkloadOp<A, K>> {
+    let mut rng = StdRng::seed_from_u64(DATASET_SEED ^ tree_points.len() as u64);
+
+    let point_of = |item: Item| {
+        let idx = item as usize;
+        if idx < tree_points.len() {
+            tree_points[idx]
+        } else {
+            new_points[(idx - tree_points.len()) % new_points.len()]
+        }
+    };
+
+    let mut live: Vec<Item> = (0..tree_points.len() as Item).collect();
+    let mut next_item = tree_points.len() as Item;
+    let mut query_idx = 0;
+    let mut ops = Vec::with_capacity(qty);
+
+    for _ in 0..qty {
+        let roll = rng.gen_range(0..mix.total());
+
+        if roll < mix.add {
+            ops.push(WorkloadOp::Add(next_item, point_of(next_item)));
+            live.push(next_item);
+            next_item += 1;
+        } else if roll >= mix.add + mix.nearest_n && !live.is_empty() {
+            let item = live.swap_remove(rng.gen_range(0..live.len()));
+            ops.push(WorkloadOp::Remove(item, point_of(item)));
+        } else {
+            ops.push(WorkloadOp::NearestN(queries[query_idx % queries.len()]));
+            query_idx += 1;
+        }
+    }
+
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 10 x 10 grid of points, 1 apart
+    fn grid() -> Vec<[f64; 2]> {
+        (0..100)
+            .map(|idx| [f64::from(idx % 10), f64::from(idx / 10)])
+            .collect()
+    }
+
+    #[test]
+    fn parses_operation_mixes() {
+        let expected = OperationMix {
+            add: 50,
+            nearest_n: 40,
+            remove: 10,
+        };
+        assert_eq!("50:40:10".parse::<OperationMix>(), Ok(expected));
+        assert_eq!(" 50 : 40 : 10 ".parse::<OperationMix>(), Ok(expected));
+        assert_eq!(expected.to_string().parse::<OperationMix>(), Ok(expected));
+
+        assert_eq!(
+            "0:100:0".parse::<OperationMix>().map(|mix| mix.nearest_n),
+            Ok(100)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_operation_mixes() {
+        for s in ["", "50:40", "50:40:10:0", "a:b:c", "50:-40:10", "0:0:0"] {
+            assert_eq!(s.parse::<OperationMix>(), Err(InvalidMix(s.to_string())));
+        }
+    }
+
+    #[test]
+    fn workload_adds_new_items_and_removes_live_ones() {
+        let tree_points: Vec<[f64; 2]> = (0..10).map(|idx| [f64::from(idx), 0.0]).collect();
+        let new_points = [[0.0, 1.0], [1.0, 1.0]];
+        let queries = [[0.5, 0.5]];
+        let mix = OperationMix {
+            add: 0,
+            nearest_n: 0,
+            remove: 100,
+        };
+
+        let ops = workload_ops(mix, &tree_points, &new_points, &queries, 15);
+
+        let mut removed: Vec<Item> = ops[..10]
+            .iter()
+            .map(|op| match op {
+                WorkloadOp::Remove(item, point) => {
+                    assert_eq!(tree_points[*item as usize], *point);
+                    *item
+                }
+                _ => panic!("expected a remove, got {:?}", op),
+            })
+            .collect();
+        removed.sort_unstable();
+        assert_eq!(removed, (0..10).collect::<Vec<Item>>());
+
+        // Once the tree is empty, removes fall back to queries
+        assert!(ops[10..]
+            .iter()
+            .all(|op| *op == WorkloadOp::NearestN([0.5, 0.5])));
+
+        let mix = OperationMix {
+            add: 100,
+            nearest_n: 0,
+            remove: 0,
+        };
+        let ops = workload_ops(mix, &tree_points, &new_points, &queries, 3);
+        assert_eq!(
+            ops,
+            [
+                WorkloadOp::Add(10, [0.0, 1.0]),
+                WorkloadOp::Add(11, [1.0, 1.0]),
+                WorkloadOp::Add(12, [0.0, 1.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn workload_is_repeatable() {
+        let tree_points: Vec<[f64; 2]> = (0..100).map(|idx| [f64::from(idx), 0.0]).collect();
+        let new_points = [[0.0, 1.0]];
+        let queries = [[0.5, 0.5], [1.5, 0.5]];
+        let mix = "50:40:10".parse().unwrap();
+
+        assert_eq!(
+            workload_ops(mix, &tree_points, &new_points, &queries, 1_000),
+            workload_ops(mix, &tree_points, &new_points, &queries, 1_000)
+        );
+    }
+
+    #[test]
+    fn removes_at_least_one_point() {
+        let points = grid();
+        for order in REMOVAL_ORDERS {
+            let removal = Removal { percent: 1, order };
+            assert_eq!(removal.sequence(&points[..50]).len(), 1);
+
+            let removal = Removal { percent: 50, order };
+            assert_eq!(removal.sequence(&points[..10]).len(), 5);
+        }
+    }
+
+    #[test]
+    fn removes_a_percentage_of_at_least_one_point() {
+        let removal = Removal {
+            percent: 10,
+            order: RemovalOrder::Random,
+        };
+        assert_eq!(removal.qty(1_000), 100);
+        assert_eq!(removal.qty(5), 1);
+        assert_eq!(removal.qty(1), 1);
+
+        let removal = Removal {
+            percent: 50,
+            order: RemovalOrder::Clustered,
+        };
+        assert_eq!(removal.qty(101), 50);
+    }
+
+    #[test]
+    fn random_removal_samples_distinct_points() {
+        let points = grid();
+        let removal = Removal {
+            percent: 10,
+            order: RemovalOrder::Random,
+        };
+
+        let sequence = removal.sequence(&points);
+
+        assert_eq!(sequence.len(), 10);
+        for (item, point) in &sequence {
+            assert_eq!(points[*item as usize], *point);
+        }
+
+        let mut items: Vec<Item> = sequence.iter().map(|(item, _)| *item).collect();
+        assert_ne!(items, (0..10).collect::<Vec<Item>>());
+        items.sort_unstable();
+        items.dedup();
+        assert_eq!(items.len(), 10);
+
+        assert_eq!(removal.sequence(&points), sequence);
+    }
+
+    #[test]
+    fn clustered_removal_is_nearest_first() {
+        let points = grid();
+        let removal = Removal {
+            percent: 10,
+            order: RemovalOrder::Clustered,
+        };
+
+        let sequence = removal.sequence(&points);
+
+        assert_eq!(sequence.len(), 10);
+        assert_eq!(sequence[0], (0, [0.0, 0.0]));
+
+        let distances: Vec<f64> = sequence.iter().map(|(_, [x, y])| x * x + y * y).collect();
+        assert!(distances.windows(2).all(|pair| pair[0] <= pair[1]));
+        // The 10 points nearest the corner all lie within 3 of it
+        assert!(distances.iter().all(|&distance| distance <= 9.0));
+    }
+}